@@ -0,0 +1,41 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use pokerface::{create_deck, evaluate_hand, evaluate_strength, Card};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+// The same fixed set of random seven card hands for both evaluators
+fn seven_card_hands() -> Vec<Vec<Card>> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut deck = create_deck();
+    (0..1000)
+        .map(|_| {
+            deck.shuffle(&mut rng);
+            deck[..7].to_vec()
+        })
+        .collect()
+}
+
+fn bench_evaluators(c: &mut Criterion) {
+    let hands = seven_card_hands();
+    let mut group = c.benchmark_group("seven_card_hands");
+
+    group.bench_function("evaluate_hand", |b| {
+        b.iter(|| {
+            for cards in &hands {
+                black_box(evaluate_hand(black_box(&cards[..2]), black_box(&cards[2..])));
+            }
+        })
+    });
+
+    group.bench_function("evaluate_strength", |b| {
+        b.iter(|| {
+            for cards in &hands {
+                black_box(evaluate_strength(black_box(&cards[..2]), black_box(&cards[2..])));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_evaluators);
+criterion_main!(benches);
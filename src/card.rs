@@ -0,0 +1,183 @@
+use std::fmt;
+
+/// A single playing card.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Card {
+    // value from 2 to 14, representing 2 to 10 and Jack, Queen, King, Ace, or 15 for a joker
+    pub value: u8,
+    // suit from 0 to 3, representing 4 suits, or telling jokers apart
+    pub suit: u8,
+}
+
+impl Card {
+    /// The value of every joker.
+    pub const JOKER_VALUE: u8 = 15;
+
+    /// The joker numbered `index`, from 0 to 3, so a deck can hold several distinct jokers.
+    pub fn joker(index: u8) -> Card {
+        Card {
+            value: Card::JOKER_VALUE,
+            suit: index,
+        }
+    }
+
+    /// A placeholder for a card that is in play but not known, written `??`.
+    pub const UNKNOWN: Card = Card { value: 0, suit: 0 };
+
+    /// Whether the card is a joker.
+    pub fn is_joker(self) -> bool {
+        self.value == Card::JOKER_VALUE
+    }
+
+    /// Whether the card is the [`Card::UNKNOWN`] placeholder.
+    pub fn is_unknown(self) -> bool {
+        self.value == 0
+    }
+}
+
+/// Formats the card the way [`parse_cards`] reads it, such as `Ah`, `Td`, `X1` or `??`.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_unknown() {
+            return write!(f, "??");
+        }
+        if self.is_joker() {
+            return write!(f, "X{}", self.suit + 1);
+        }
+        let value = match self.value {
+            2..=9 => (b'0' + self.value) as char,
+            10 => 'T',
+            11 => 'J',
+            12 => 'Q',
+            13 => 'K',
+            _ => 'A',
+        };
+        let suit = ['h', 'd', 'c', 's'][self.suit as usize];
+        write!(f, "{}{}", value, suit)
+    }
+}
+
+/// Parses a whitespace separated list of cards such as `"Ah Kd Tc"`.
+///
+/// Values are `2`-`9`, `T`, `J`, `Q`, `K`, `A` and suits are `h`, `d`, `c`, `s`.
+/// Jokers are written `X1` to `X4`, and `??` stands for a card that is dealt but not known,
+/// giving [`Card::UNKNOWN`]. Tokens that are not exactly two characters long are skipped.
+///
+/// # Panics
+///
+/// Panics if a token has an unknown value or suit character.
+pub fn parse_cards(input: &str) -> Vec<Card> {
+    input
+        .split_whitespace()
+        .filter_map(|card_str| {
+            if card_str.len() != 2 {
+                None
+            } else if card_str == "??" {
+                Some(Card::UNKNOWN)
+            } else if let Some(index) = card_str.strip_prefix('X') {
+                match index {
+                    "1" | "2" | "3" | "4" => Some(Card::joker(index.as_bytes()[0] - b'1')),
+                    _ => panic!("Invalid joker number"),
+                }
+            } else {
+                let bytes = card_str.as_bytes();
+                let value = parse_value(bytes[0] as char).expect("Invalid card value");
+                let suit = match bytes[1] as char {
+                    'h' => 0,
+                    'd' => 1,
+                    'c' => 2,
+                    's' => 3,
+                    _ => panic!("Invalid card suit"),
+                };
+                Some(Card { value, suit })
+            }
+        })
+        .collect()
+}
+
+// The cards of `cards` that are known, leaving out every placeholder
+pub(crate) fn known_cards(cards: &[Card]) -> Vec<Card> {
+    cards
+        .iter()
+        .copied()
+        .filter(|card| !card.is_unknown())
+        .collect()
+}
+
+// Panics if any of `cards` is a placeholder, in a `game` that cannot deal unknown cards
+pub(crate) fn check_known(cards: &[Card], game: &str) {
+    assert!(
+        cards.iter().all(|card| !card.is_unknown()),
+        "Unknown cards are not supported in {}",
+        game
+    );
+}
+
+/// Parses a card value written as in [`parse_cards`], from `2` to `A`, giving 2 to 14.
+///
+/// Returns `None` for any other character.
+pub fn parse_value(c: char) -> Option<u8> {
+    match c {
+        '2'..='9' => Some(c as u8 - b'0'),
+        'T' => Some(10),
+        'J' => Some(11),
+        'Q' => Some(12),
+        'K' => Some(13),
+        'A' => Some(14),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cards() {
+        assert_eq!(
+            parse_cards("Ah Td 2c 9s"),
+            vec![
+                Card { value: 14, suit: 0 },
+                Card { value: 10, suit: 1 },
+                Card { value: 2, suit: 2 },
+                Card { value: 9, suit: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let cards = parse_cards("Ah Td 2c 9s Qh");
+        let text: Vec<String> = cards.iter().map(|card| card.to_string()).collect();
+        assert_eq!(text.join(" "), "Ah Td 2c 9s Qh");
+    }
+
+    #[test]
+    fn test_jokers() {
+        let cards = parse_cards("X1 X2 Ah");
+        assert_eq!(cards[0], Card::joker(0));
+        assert_eq!(cards[1], Card::joker(1));
+        assert!(cards[0].is_joker() && !cards[2].is_joker());
+        assert_eq!(cards[1].to_string(), "X2");
+    }
+
+    #[test]
+    fn test_unknown_cards() {
+        let cards = parse_cards("Ah ??");
+        assert_eq!(cards, vec![Card { value: 14, suit: 0 }, Card::UNKNOWN]);
+        assert!(cards[1].is_unknown() && !cards[0].is_unknown());
+        assert_eq!(cards[1].to_string(), "??");
+        assert_eq!(known_cards(&cards), vec![cards[0]]);
+    }
+
+    #[test]
+    fn test_parse_cards_empty() {
+        assert_eq!(parse_cards(""), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid card suit")]
+    fn test_parse_cards_invalid_suit() {
+        parse_cards("Ax");
+    }
+}
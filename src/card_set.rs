@@ -0,0 +1,258 @@
+use std::ops::{BitAnd, BitOr, Sub};
+
+use crate::card::Card;
+
+/// A set of cards stored as a 64 bit mask.
+///
+/// Each suit takes 16 bits, with bit `suit * 16 + value - 2` set for a card, so the
+/// ranks held in one suit can be read as a 13 bit mask with [`CardSet::suit_mask`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+// The 13 rank bits of every suit
+const FULL_DECK: u64 = 0x1FFF_1FFF_1FFF_1FFF;
+
+// The rank bits from six to ace of every suit
+const SHORT_DECK: u64 = 0x1FF0_1FF0_1FF0_1FF0;
+
+fn bit(card: Card) -> u64 {
+    // Only real cards have a bit, never the `??` placeholder
+    assert!(card.value >= 2, "Invalid card in a card set: {}", card);
+    1 << (card.suit as u64 * 16 + card.value as u64 - 2)
+}
+
+impl CardSet {
+    /// The empty set.
+    pub const EMPTY: CardSet = CardSet(0);
+
+    /// All 52 cards of a standard deck.
+    pub fn full_deck() -> CardSet {
+        CardSet(FULL_DECK)
+    }
+
+    /// The 36 cards from six to ace of a Short Deck.
+    pub fn short_deck() -> CardSet {
+        CardSet(SHORT_DECK)
+    }
+
+    /// Builds a set from its raw bitmask.
+    pub fn from_bits(bits: u64) -> CardSet {
+        CardSet(bits)
+    }
+
+    /// The raw bitmask of the set.
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Adds `card`, returning `true` if it was not already in the set.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let added = !self.contains(card);
+        self.0 |= bit(card);
+        added
+    }
+
+    /// Removes `card`, returning `true` if it was in the set.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let removed = self.contains(card);
+        self.0 &= !bit(card);
+        removed
+    }
+
+    /// Whether `card` is in the set.
+    pub fn contains(self, card: Card) -> bool {
+        self.0 & bit(card) != 0
+    }
+
+    /// The cards in either `self` or `other`.
+    pub fn union(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+
+    /// The cards in both `self` and `other`.
+    pub fn intersection(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+
+    /// The cards of `self` that are not in `other`.
+    pub fn difference(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+
+    /// Number of cards in the set.
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Whether the set holds no card.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The ranks held in `suit`, with bit `value - 2` set for each card.
+    pub fn suit_mask(self, suit: u8) -> u16 {
+        (self.0 >> (suit as u64 * 16)) as u16 & 0x1FFF
+    }
+
+    /// Iterates over the cards, ordered by suit and then by value.
+    pub fn iter(self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+/// Iterator over the cards of a [`CardSet`].
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Card> {
+        if self.0 == 0 {
+            return None;
+        }
+        let index = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(Card {
+            value: index % 16 + 2,
+            suit: index / 16,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CardSetIter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = Card>>(iter: I) -> CardSet {
+        let mut set = CardSet::EMPTY;
+        set.extend(iter);
+        set
+    }
+}
+
+impl<'a> FromIterator<&'a Card> for CardSet {
+    fn from_iter<I: IntoIterator<Item = &'a Card>>(iter: I) -> CardSet {
+        iter.into_iter().copied().collect()
+    }
+}
+
+impl Extend<Card> for CardSet {
+    fn extend<I: IntoIterator<Item = Card>>(&mut self, iter: I) {
+        for card in iter {
+            self.0 |= bit(card);
+        }
+    }
+}
+
+impl From<&[Card]> for CardSet {
+    fn from(cards: &[Card]) -> CardSet {
+        cards.iter().collect()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        self.union(other)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        self.intersection(other)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        self.difference(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use crate::deck::{create_deck, create_short_deck};
+
+    fn set(input: &str) -> CardSet {
+        parse_cards(input).iter().collect()
+    }
+
+    #[test]
+    fn test_full_deck_matches_create_deck() {
+        let deck = CardSet::full_deck();
+        assert_eq!(deck.len(), 52);
+        assert_eq!(deck.iter().collect::<Vec<_>>(), create_deck());
+    }
+
+    #[test]
+    fn test_short_deck_matches_create_short_deck() {
+        let deck = CardSet::short_deck();
+        assert_eq!(deck.len(), 36);
+        assert_eq!(deck.iter().collect::<Vec<_>>(), create_short_deck());
+    }
+
+    #[test]
+    fn test_insert_and_remove() {
+        let mut cards = CardSet::EMPTY;
+        let ace = Card { value: 14, suit: 3 };
+        assert!(cards.insert(ace));
+        assert!(!cards.insert(ace));
+        assert!(cards.contains(ace));
+        assert_eq!(cards.len(), 1);
+        assert!(cards.remove(ace));
+        assert!(!cards.remove(ace));
+        assert!(cards.is_empty());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set("Ah Kh Qd");
+        let b = set("Kh Qd 2c");
+        assert_eq!(a | b, set("Ah Kh Qd 2c"));
+        assert_eq!(a & b, set("Kh Qd"));
+        assert_eq!(a - b, set("Ah"));
+        assert_eq!(a.union(b).len(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid card in a card set: ??")]
+    fn test_unknown_card_has_no_bit() {
+        CardSet::EMPTY.contains(Card::UNKNOWN);
+    }
+
+    #[test]
+    fn test_suit_mask() {
+        let cards = set("Ah 2h 5d Kd");
+        assert_eq!(cards.suit_mask(0), 1 << 12 | 1);
+        assert_eq!(cards.suit_mask(1), 1 << 11 | 1 << 3);
+        assert_eq!(cards.suit_mask(2), 0);
+    }
+
+    #[test]
+    fn test_iter_round_trip() {
+        let cards = parse_cards("2h Ah 3d Ts");
+        let collected: Vec<Card> = CardSet::from(&cards[..]).into_iter().collect();
+        assert_eq!(collected, parse_cards("2h Ah 3d Ts"));
+        assert_eq!(set("2h Ah 3d Ts").iter().len(), 4);
+    }
+}
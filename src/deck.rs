@@ -0,0 +1,100 @@
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::wild::Wilds;
+
+/// Creates a standard ordered 52-card deck.
+pub fn create_deck() -> Vec<Card> {
+    let mut deck = Vec::new();
+    for suit in 0..4 {
+        for value in 2..=14 {
+            deck.push(Card { value, suit });
+        }
+    }
+    deck
+}
+
+/// Creates a standard deck with the jokers of `wilds` added at the end.
+///
+/// Wild values need no change to the deck, since those cards are only wild when evaluated.
+pub fn create_deck_with(wilds: Wilds) -> Vec<Card> {
+    let mut deck = create_deck();
+    deck.extend((0..wilds.jokers).map(Card::joker));
+    deck
+}
+
+/// Creates a standard deck without the `dead` cards, which are out of play.
+pub fn create_deck_without(dead: &[Card]) -> Vec<Card> {
+    let mut deck = create_deck();
+    remove_known_cards(&mut deck, dead, &[]);
+    deck
+}
+
+/// Creates an ordered 36-card Short Deck, without the twos to fives.
+pub fn create_short_deck() -> Vec<Card> {
+    let mut deck = create_deck();
+    deck.retain(|card| card.value >= 6);
+    deck
+}
+
+/// Removes the hero's hand and the board cards from `deck`, skipping [`Card::UNKNOWN`]
+/// placeholders.
+pub fn remove_known_cards(deck: &mut Vec<Card>, hand: &[Card], board: &[Card]) {
+    let known: CardSet = hand
+        .iter()
+        .chain(board)
+        .filter(|card| !card.is_unknown())
+        .collect();
+    deck.retain(|card| !known.contains(*card));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_deck() {
+        let deck = create_deck();
+        assert_eq!(deck.len(), 52);
+        for (i, card) in deck.iter().enumerate() {
+            assert!(!deck[i + 1..].contains(card));
+        }
+    }
+
+    #[test]
+    fn test_remove_known_cards() {
+        let mut deck = create_deck();
+        let hand = [Card { value: 14, suit: 0 }, Card { value: 14, suit: 1 }];
+        let board = [Card { value: 2, suit: 2 }];
+        remove_known_cards(&mut deck, &hand, &board);
+        assert_eq!(deck.len(), 49);
+        assert!(!deck.contains(&hand[0]));
+        assert!(!deck.contains(&board[0]));
+    }
+
+    #[test]
+    fn test_create_deck_without() {
+        let dead = [Card { value: 13, suit: 3 }, Card { value: 7, suit: 2 }];
+        let deck = create_deck_without(&dead);
+        assert_eq!(deck.len(), 50);
+        assert!(!deck.contains(&dead[0]) && !deck.contains(&dead[1]));
+
+        let mut deck = create_deck();
+        remove_known_cards(&mut deck, &[dead[0], Card::UNKNOWN], &[]);
+        assert_eq!(deck.len(), 51);
+    }
+
+    #[test]
+    fn test_create_short_deck() {
+        let deck = create_short_deck();
+        assert_eq!(deck.len(), 36);
+        assert!(deck.iter().all(|card| (6..=14).contains(&card.value)));
+    }
+
+    #[test]
+    fn test_create_deck_with_jokers() {
+        let deck = create_deck_with(Wilds::jokers(2).with_wild_value(2));
+        assert_eq!(deck.len(), 54);
+        assert_eq!(deck[52..], [Card::joker(0), Card::joker(1)]);
+        assert_eq!(create_deck_with(Wilds::NONE), create_deck());
+    }
+}
@@ -0,0 +1,188 @@
+use std::fmt;
+
+use crate::evaluator::HandRank;
+
+fn value_name(value: u8) -> &'static str {
+    match value {
+        2 => "Two",
+        3 => "Three",
+        4 => "Four",
+        5 => "Five",
+        6 => "Six",
+        7 => "Seven",
+        8 => "Eight",
+        9 => "Nine",
+        10 => "Ten",
+        11 => "Jack",
+        12 => "Queen",
+        13 => "King",
+        _ => "Ace",
+    }
+}
+
+fn value_plural(value: u8) -> String {
+    match value {
+        6 => "Sixes".to_string(),
+        _ => format!("{}s", value_name(value)),
+    }
+}
+
+// "Ace kicker" or "King, Nine and Seven kickers"
+fn kickers(values: &[u8]) -> String {
+    let names: Vec<&str> = values.iter().map(|&value| value_name(value)).collect();
+    match names.split_last() {
+        Some((last, [])) => format!("{} kicker", last),
+        Some((last, rest)) => format!("{} and {} kickers", rest.join(", "), last),
+        None => String::new(),
+    }
+}
+
+impl HandRank {
+    /// The name of the hand category, such as `"Two Pair"`.
+    pub fn category_name(&self) -> &'static str {
+        match self {
+            HandRank::HighCard(..) => "High Card",
+            HandRank::OnePair(..) => "One Pair",
+            HandRank::TwoPair(..) => "Two Pair",
+            HandRank::ThreeOfAKind(..) => "Three of a Kind",
+            HandRank::Straight(_) => "Straight",
+            HandRank::Flush(..) => "Flush",
+            HandRank::FullHouse(..) => "Full House",
+            HandRank::FourOfAKind(..) => "Four of a Kind",
+            HandRank::StraightFlush(_) => "Straight Flush",
+            HandRank::RoyalFlush => "Royal Flush",
+            HandRank::FiveOfAKind(_) => "Five of a Kind",
+        }
+    }
+
+    /// Describes the hand without kickers, such as `"Two Pair, Kings and Sevens"`.
+    pub fn short_description(&self) -> String {
+        let category = self.category_name();
+        match *self {
+            HandRank::HighCard(high, ..) => format!("{}, {}", category, value_name(high)),
+            HandRank::OnePair(pair, ..) => format!("{}, {}", category, value_plural(pair)),
+            HandRank::TwoPair(high, low, _) => {
+                format!(
+                    "{}, {} and {}",
+                    category,
+                    value_plural(high),
+                    value_plural(low)
+                )
+            }
+            HandRank::ThreeOfAKind(trips, ..) => format!("{}, {}", category, value_plural(trips)),
+            HandRank::Straight(high) | HandRank::StraightFlush(high) => {
+                format!("{}, {} high", category, value_name(high))
+            }
+            HandRank::Flush(high, ..) => format!("{}, {} high", category, value_name(high)),
+            HandRank::FullHouse(trips, pair) => {
+                format!(
+                    "{}, {} full of {}",
+                    category,
+                    value_plural(trips),
+                    value_plural(pair)
+                )
+            }
+            HandRank::FourOfAKind(quads, _) => format!("{}, {}", category, value_plural(quads)),
+            HandRank::FiveOfAKind(value) => format!("{}, {}", category, value_plural(value)),
+            HandRank::RoyalFlush => category.to_string(),
+        }
+    }
+
+    /// Describes the hand with every card that breaks ties, such as
+    /// `"Two Pair, Kings and Sevens, Ace kicker"`.
+    ///
+    /// This is also what the `Display` implementation prints.
+    pub fn long_description(&self) -> String {
+        let short = self.short_description();
+        match *self {
+            HandRank::HighCard(_, b, c, d, e) => format!("{}, {}", short, kickers(&[b, c, d, e])),
+            HandRank::OnePair(_, a, b, c) => format!("{}, {}", short, kickers(&[a, b, c])),
+            HandRank::TwoPair(_, _, kicker) => format!("{}, {}", short, kickers(&[kicker])),
+            HandRank::ThreeOfAKind(_, a, b) => format!("{}, {}", short, kickers(&[a, b])),
+            HandRank::Flush(a, b, c, d, e) => {
+                let names: Vec<&str> = [a, b, c, d, e]
+                    .iter()
+                    .map(|&value| value_name(value))
+                    .collect();
+                format!("{}, {}", self.category_name(), names.join(" "))
+            }
+            HandRank::FourOfAKind(_, kicker) => format!("{}, {}", short, kickers(&[kicker])),
+            _ => short,
+        }
+    }
+}
+
+impl fmt::Display for HandRank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.long_description())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_descriptions() {
+        let cases = [
+            (HandRank::HighCard(14, 13, 9, 7, 3), "High Card, Ace"),
+            (HandRank::OnePair(6, 14, 9, 7), "One Pair, Sixes"),
+            (HandRank::TwoPair(13, 7, 14), "Two Pair, Kings and Sevens"),
+            (HandRank::ThreeOfAKind(2, 14, 9), "Three of a Kind, Twos"),
+            (HandRank::Straight(5), "Straight, Five high"),
+            (HandRank::Flush(14, 11, 9, 7, 3), "Flush, Ace high"),
+            (
+                HandRank::FullHouse(13, 7),
+                "Full House, Kings full of Sevens",
+            ),
+            (HandRank::FourOfAKind(14, 13), "Four of a Kind, Aces"),
+            (HandRank::StraightFlush(9), "Straight Flush, Nine high"),
+            (HandRank::RoyalFlush, "Royal Flush"),
+            (HandRank::FiveOfAKind(14), "Five of a Kind, Aces"),
+        ];
+        for (rank, expected) in cases {
+            assert_eq!(rank.short_description(), expected);
+        }
+    }
+
+    #[test]
+    fn test_long_descriptions() {
+        let cases = [
+            (
+                HandRank::HighCard(14, 13, 9, 7, 3),
+                "High Card, Ace, King, Nine, Seven and Three kickers",
+            ),
+            (
+                HandRank::OnePair(6, 14, 9, 7),
+                "One Pair, Sixes, Ace, Nine and Seven kickers",
+            ),
+            (
+                HandRank::TwoPair(13, 7, 14),
+                "Two Pair, Kings and Sevens, Ace kicker",
+            ),
+            (
+                HandRank::ThreeOfAKind(2, 14, 9),
+                "Three of a Kind, Twos, Ace and Nine kickers",
+            ),
+            (HandRank::Straight(5), "Straight, Five high"),
+            (
+                HandRank::Flush(14, 11, 9, 7, 3),
+                "Flush, Ace Jack Nine Seven Three",
+            ),
+            (
+                HandRank::FullHouse(13, 7),
+                "Full House, Kings full of Sevens",
+            ),
+            (
+                HandRank::FourOfAKind(14, 13),
+                "Four of a Kind, Aces, King kicker",
+            ),
+            (HandRank::StraightFlush(9), "Straight Flush, Nine high"),
+            (HandRank::RoyalFlush, "Royal Flush"),
+        ];
+        for (rank, expected) in cases {
+            assert_eq!(rank.long_description(), expected);
+            assert_eq!(rank.to_string(), expected);
+        }
+    }
+}
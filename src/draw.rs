@@ -0,0 +1,501 @@
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+use crate::card::{check_known, Card};
+use crate::deck::{create_deck, remove_known_cards};
+use crate::evaluator::HandRank;
+use crate::lookup::evaluate_strength;
+use crate::low::evaluate_deuce_seven;
+use crate::simulation::{
+    check_player_count, distinct_cards, rates_of, sample_deals, seat_error, split_showdown,
+    tally_equity, Budget, Equity, Tally, SEAT_SCORES,
+};
+
+/// Estimates the hero's chances in deuce-to-seven lowball with `draws` drawing rounds, 1
+/// for single draw and 3 for triple draw, against `num_players - 1` opponents.
+///
+/// The hero keeps the `kept` cards and draws the rest of a five card hand on the first
+/// draw, from the deck left once the kept cards are removed. Opponents are dealt five
+/// random cards. On every other draw, the hero's later ones included, players stand pat
+/// on a nine low or better and otherwise keep their unpaired cards from deuce to eight.
+/// When the deck runs out, the discards are shuffled into a new one.
+///
+/// Returns `(win_rate, tie_rate, loss_rate)`, each between 0 and 1.
+///
+/// # Panics
+///
+/// Panics if more than 5 cards are kept or a kept card is unknown, or if `num_players` is
+/// not from 2 to 23 or the deck cannot deal every hand.
+pub fn simulate_deuce_seven(kept: &[Card], draws: usize, num_players: usize) -> (f64, f64, f64) {
+    rates_of(calculate_deuce_seven(
+        kept,
+        &[],
+        draws,
+        num_players,
+        Budget::DEFAULT,
+    ))
+}
+
+/// Like [`simulate_deuce_seven`], sampling random deals until `budget` is spent and never
+/// dealing the `dead` cards.
+///
+/// # Panics
+///
+/// Panics if more than 5 cards are kept or a kept card is unknown, if a card is given twice
+/// among `kept` and `dead`, or if `num_players` is not from 2 to 23 or the deck cannot deal
+/// every hand.
+pub fn calculate_deuce_seven(
+    kept: &[Card],
+    dead: &[Card],
+    draws: usize,
+    num_players: usize,
+    budget: Budget,
+) -> Equity {
+    assert!(
+        kept.len() <= 5,
+        "Invalid hand length: expected at most 5 cards, found {}",
+        kept.len()
+    );
+    check_known(kept, "deuce-to-seven draw");
+    distinct_cards(kept.iter().chain(dead));
+
+    let mut deck = create_deck();
+    remove_known_cards(&mut deck, kept, dead);
+    let tally = run_draws(
+        &deck,
+        kept,
+        draws,
+        num_players,
+        budget,
+        cards_to_keep,
+        |cards| evaluate_deuce_seven(cards),
+    );
+    tally_equity(tally)
+}
+
+/// One way to play a Five Card Draw hand and how it fares.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DiscardOption {
+    // The cards held, in the order of the original hand
+    pub hold: Vec<Card>,
+    // The hero's chances when holding them
+    pub equity: Equity,
+}
+
+/// Simulates every one of the 32 ways to hold cards from a Five Card Draw `hand` against
+/// `num_players - 1` opponents, with a single draw.
+///
+/// Each hold is played out 100000 times, or within a given budget with
+/// [`analyze_discards_with_budget`]. The discarded cards are out of play, and the opponents
+/// are dealt five random cards from the rest of the deck. They stand pat on a
+/// straight or better, and otherwise keep any paired cards or four to a flush, or else
+/// their highest card.
+///
+/// The options are ordered from the largest share of the pot down, so split pots count
+/// and the first one is the recommended hold.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold exactly 5 cards or holds an unknown card, or if
+/// `num_players` is not from 2 to 23 or the deck cannot deal every hand.
+pub fn analyze_discards(hand: &[Card], num_players: usize) -> Vec<DiscardOption> {
+    analyze_discards_with_budget(hand, &[], num_players, Budget::iterations(100000))
+}
+
+/// Like [`analyze_discards`], playing each hold out until `budget` is spent and never
+/// dealing the `dead` cards. With a seed every hold is played against the same random
+/// deals.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold exactly 5 cards or holds an unknown card, if a card is
+/// given twice among `hand` and `dead`, or if `num_players` is not from 2 to 23 or the deck
+/// cannot deal every hand.
+pub fn analyze_discards_with_budget(
+    hand: &[Card],
+    dead: &[Card],
+    num_players: usize,
+    budget: Budget,
+) -> Vec<DiscardOption> {
+    assert_eq!(
+        hand.len(),
+        5,
+        "Invalid hand length: expected 5 cards, found {}",
+        hand.len()
+    );
+    check_known(hand, "Five Card Draw");
+    distinct_cards(hand.iter().chain(dead));
+
+    let mut deck = create_deck();
+    remove_known_cards(&mut deck, hand, dead);
+
+    let mut options: Vec<DiscardOption> = (0..32u32)
+        .map(|holds| {
+            let hold: Vec<Card> = (0..5)
+                .filter(|i| holds & (1 << i) != 0)
+                .map(|i| hand[i])
+                .collect();
+            let tally = run_draws(
+                &deck,
+                &hold,
+                1,
+                num_players,
+                budget,
+                high_cards_to_keep,
+                |cards| evaluate_strength(cards, &[]),
+            );
+            DiscardOption {
+                hold,
+                equity: tally_equity(tally),
+            }
+        })
+        .collect();
+    sort_by_equity(&mut options);
+    options
+}
+
+// Orders `options` from the largest share of the pot down, and on equal shares from the
+// highest win rate down
+pub(crate) fn sort_by_equity(options: &mut [DiscardOption]) {
+    options.sort_by(|a, b| {
+        (b.equity.pot_share, b.equity.win_rate)
+            .partial_cmp(&(a.equity.pot_share, a.equity.win_rate))
+            .unwrap()
+    });
+}
+
+// Plays out random draw hands dealt from `deck` until `budget` is spent, and counts the
+// hero's `SeatScores`. The hero's first draw completes the `kept` cards, opponents
+// are dealt five cards, and every other draw replaces the cards that `keep` does not hold.
+fn run_draws<T: Ord>(
+    deck: &[Card],
+    kept: &[Card],
+    draws: usize,
+    num_players: usize,
+    budget: Budget,
+    keep: fn(&[Card; 5]) -> [bool; 5],
+    evaluate: impl Fn(&[Card; 5]) -> T + Sync,
+) -> Tally<SEAT_SCORES> {
+    check_player_count(num_players);
+    // Every hand is dealt, and the first player to draw may replace all five cards, before
+    // any discard can be shuffled back in
+    let needed = 5 - kept.len() + 5 * (num_players - 1) + 5;
+    assert!(
+        needed <= deck.len(),
+        "Not enough cards to deal: {} needed, {} left",
+        needed,
+        deck.len()
+    );
+    sample_deals(
+        budget,
+        seat_error,
+        || (Vec::new(), Vec::new(), vec![[deck[0]; 5]; num_players]),
+        |(stub, discards, hands), rng| {
+            stub.clear();
+            stub.extend_from_slice(deck);
+            stub.shuffle(rng);
+            discards.clear();
+
+            hands[0][..kept.len()].copy_from_slice(kept);
+            for card in &mut hands[0][kept.len()..] {
+                *card = deal(stub, discards, rng);
+            }
+            for hand in &mut hands[1..] {
+                for card in hand.iter_mut() {
+                    *card = deal(stub, discards, rng);
+                }
+            }
+
+            for round in 0..draws {
+                let first_player = if round == 0 { 1 } else { 0 };
+                for hand in &mut hands[first_player..] {
+                    draw(hand, keep(hand), stub, discards, rng);
+                }
+            }
+
+            split_showdown(evaluate(&hands[0]), hands[1..].iter().map(&evaluate))
+        },
+    )
+}
+
+// Replaces the cards the player does not keep. The discards only go back into play after
+// the replacements are dealt.
+fn draw(
+    hand: &mut [Card; 5],
+    keep: [bool; 5],
+    stub: &mut Vec<Card>,
+    discards: &mut Vec<Card>,
+    rng: &mut dyn RngCore,
+) {
+    let mut thrown = [hand[0]; 5];
+    let mut thrown_count = 0;
+    for (card, keep) in hand.iter_mut().zip(keep) {
+        if !keep {
+            thrown[thrown_count] = *card;
+            thrown_count += 1;
+            *card = deal(stub, discards, rng);
+        }
+    }
+    discards.extend_from_slice(&thrown[..thrown_count]);
+}
+
+// The cards a player keeps: every card with a nine low or better, and otherwise the
+// unpaired cards from deuce to eight
+fn cards_to_keep(hand: &[Card; 5]) -> [bool; 5] {
+    if let HandRank::HighCard(high, ..) = evaluate_deuce_seven(hand).high_rank() {
+        if high <= 9 {
+            return [true; 5];
+        }
+    }
+
+    let mut keep = [false; 5];
+    let mut seen = 0u16;
+    for (slot, card) in hand.iter().enumerate() {
+        if card.value <= 8 && seen & (1 << card.value) == 0 {
+            keep[slot] = true;
+            seen |= 1 << card.value;
+        }
+    }
+    // Five low cards that are no pat hand make a straight or a flush, so break it up
+    if keep == [true; 5] {
+        let (highest, _) = hand
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, card)| card.value)
+            .unwrap();
+        keep[highest] = false;
+    }
+    keep
+}
+
+// The cards a Five Card Draw player keeps: every card with a straight or better, and
+// otherwise any paired cards, four to a flush or the highest card
+fn high_cards_to_keep(hand: &[Card; 5]) -> [bool; 5] {
+    if evaluate_strength(hand, &[]).rank() >= HandRank::Straight(5) {
+        return [true; 5];
+    }
+
+    let paired = hand.map(|card| {
+        hand.iter()
+            .filter(|other| other.value == card.value)
+            .count()
+            > 1
+    });
+    if paired.contains(&true) {
+        return paired;
+    }
+    for suit in 0..4 {
+        let suited = hand.map(|card| card.suit == suit);
+        if suited.iter().filter(|&&same| same).count() == 4 {
+            return suited;
+        }
+    }
+    let (highest, _) = hand
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, card)| card.value)
+        .unwrap();
+    let mut keep = [false; 5];
+    keep[highest] = true;
+    keep
+}
+
+fn deal(stub: &mut Vec<Card>, discards: &mut Vec<Card>, rng: &mut dyn RngCore) -> Card {
+    if stub.is_empty() {
+        stub.append(discards);
+        stub.shuffle(rng);
+    }
+    stub.pop().expect("Not enough cards left to deal")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn keep(hand: &str) -> [bool; 5] {
+        let cards = parse_cards(hand);
+        cards_to_keep(&[cards[0], cards[1], cards[2], cards[3], cards[4]])
+    }
+
+    #[test]
+    fn test_cards_to_keep() {
+        assert_eq!(keep("9h 7d 4c 3s 2h"), [true; 5]);
+        assert_eq!(keep("Kh 7d 4c 3s 2h"), [false, true, true, true, true]);
+        assert_eq!(keep("7h 7d Ac 3s 2h"), [true, false, false, true, true]);
+        // A straight and a flush are broken up by throwing the highest card
+        assert_eq!(keep("6h 5d 4c 3s 2h"), [false, true, true, true, true]);
+        assert_eq!(keep("8h 6h 4h 3h 2h"), [false, true, true, true, true]);
+    }
+
+    fn high_keep(hand: &str) -> [bool; 5] {
+        let cards = parse_cards(hand);
+        high_cards_to_keep(&[cards[0], cards[1], cards[2], cards[3], cards[4]])
+    }
+
+    #[test]
+    fn test_high_cards_to_keep() {
+        assert_eq!(high_keep("9h Td Jc Qs Kh"), [true; 5]);
+        assert_eq!(high_keep("Kh Kd 4c 4s 2h"), [true, true, true, true, false]);
+        assert_eq!(
+            high_keep("7h 7d Ac 3s 2h"),
+            [true, true, false, false, false]
+        );
+        assert_eq!(high_keep("Ah 9h 5h 3h Kd"), [true, true, true, true, false]);
+        assert_eq!(
+            high_keep("Ah 9d 5h 3c Kd"),
+            [true, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn test_discard_options() {
+        let hand = parse_cards("Ah Ad 7c 4s 2h");
+        let options =
+            analyze_discards_with_budget(&hand, &[], 3, Budget::iterations(2000).with_seed(1));
+        assert_eq!(options.len(), 32);
+        for pair in options.windows(2) {
+            assert!(pair[0].equity.pot_share >= pair[1].equity.pot_share);
+        }
+        // Keeping the aces beats throwing one of them away
+        let rate = |hold: &str| {
+            let hold = parse_cards(hold);
+            options
+                .iter()
+                .find(|option| option.hold == hold)
+                .unwrap()
+                .equity
+                .win_rate
+        };
+        assert!(rate("Ah Ad") > rate("Ah 7c 4s 2h"));
+        assert!(rate("Ah Ad") > rate(""));
+    }
+
+    #[test]
+    fn test_pat_straight_is_recommended() {
+        let hand = parse_cards("9h Td Jc Qs Kh");
+        let options =
+            analyze_discards_with_budget(&hand, &[], 2, Budget::iterations(2000).with_seed(1));
+        assert_eq!(options[0].hold, hand);
+    }
+
+    #[test]
+    fn test_seeded_discards_repeat() {
+        let hand = parse_cards("Kh Kd 9c 4s 2h");
+        let budget = Budget::iterations(3000).with_seed(5);
+        let options = analyze_discards_with_budget(&hand, &[], 2, budget);
+        assert_eq!(options, analyze_discards_with_budget(&hand, &[], 2, budget));
+    }
+
+    #[test]
+    fn test_seeded_draws_repeat() {
+        let hand = parse_cards("8h 5d 3c 2s");
+        let budget = Budget::iterations(5000).with_seed(4);
+        let equity = calculate_deuce_seven(&hand, &[], 3, 3, budget);
+        assert_eq!(equity, calculate_deuce_seven(&hand, &[], 3, 3, budget));
+    }
+
+    #[test]
+    fn test_pat_nuts_never_lose() {
+        let hand = parse_cards("7h 5d 4c 3s 2h");
+        let budget = Budget::iterations(10000);
+        let single = calculate_deuce_seven(&hand, &[], 1, 3, budget);
+        let triple = calculate_deuce_seven(&hand, &[], 3, 3, budget);
+        assert_eq!(single.loss_rate, 0.0);
+        assert_eq!(triple.loss_rate, 0.0);
+    }
+
+    #[test]
+    fn test_four_card_draw_beats_king_low() {
+        let hand = parse_cards("7h 5d 3c 2s");
+        let budget = Budget::iterations(10000).with_seed(1);
+        let single = calculate_deuce_seven(&hand, &[], 1, 2, budget);
+        let king_low = calculate_deuce_seven(&parse_cards("Kh 8d 5c 3s 2h"), &[], 1, 2, budget);
+        assert!(single.win_rate > 0.5);
+        assert!(king_low.win_rate < single.win_rate);
+    }
+
+    #[test]
+    fn test_dead_aces_are_never_drawn() {
+        // With the other two aces out of play, a pair of aces can no longer improve to trips
+        let hand = parse_cards("Ah Ad 7c 4s 2h");
+        let budget = Budget::iterations(5000).with_seed(2);
+        let share = |dead: &str| {
+            analyze_discards_with_budget(&hand, &parse_cards(dead), 2, budget)
+                .into_iter()
+                .find(|option| option.hold == parse_cards("Ah Ad"))
+                .unwrap()
+                .equity
+                .pot_share
+        };
+        assert!(share("Ac As") < share(""));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough cards to deal: 50 needed, 49 left")]
+    fn test_dead_cards_leave_the_deck() {
+        // Nine players need 50 of the 52 cards until the first draw, so three dead cards are
+        // too many
+        calculate_deuce_seven(&[], &[], 1, 9, Budget::iterations(10));
+        calculate_deuce_seven(&[], &parse_cards("Kh Kd Kc"), 1, 9, Budget::iterations(10));
+    }
+
+    #[test]
+    fn test_deck_reshuffles_discards() {
+        // Eight players drawing three times need more cards than the deck holds
+        let equity = calculate_deuce_seven(&[], &[], 3, 8, Budget::iterations(1000));
+        assert!((equity.win_rate + equity.tie_rate + equity.loss_rate - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown cards are not supported in Five Card Draw")]
+    fn test_discards_reject_unknown_cards() {
+        analyze_discards_with_budget(
+            &parse_cards("Ah ?? 3c 4d 5s"),
+            &[],
+            2,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown cards are not supported in deuce-to-seven draw")]
+    fn test_deuce_seven_rejects_unknown_cards() {
+        calculate_deuce_seven(&parse_cards("?? 3c"), &[], 1, 2, Budget::iterations(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: Ah")]
+    fn test_discards_reject_duplicate_cards() {
+        analyze_discards_with_budget(
+            &parse_cards("Ah Ad 7c 4s 2h"),
+            &parse_cards("Ah"),
+            2,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: 7h")]
+    fn test_deuce_seven_rejects_duplicate_cards() {
+        calculate_deuce_seven(&parse_cards("7h 7h 3c"), &[], 3, 2, Budget::iterations(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough cards to deal: 55 needed, 52 left")]
+    fn test_too_many_players_to_draw() {
+        calculate_deuce_seven(&[], &[], 1, 10, Budget::iterations(10));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid hand length")]
+    fn test_too_many_kept_cards() {
+        calculate_deuce_seven(
+            &parse_cards("7h 5d 4c 3s 2h 8d"),
+            &[],
+            1,
+            2,
+            Budget::iterations(1),
+        );
+    }
+}
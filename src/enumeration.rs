@@ -0,0 +1,158 @@
+use std::iter;
+
+use rayon::prelude::*;
+
+use crate::card::Card;
+use crate::simulation::{add_totals, Deal};
+
+// Number of ways to choose `k` of `n` cards, saturating at u128::MAX
+fn choose(n: usize, k: usize) -> u128 {
+    if k > n {
+        return 0;
+    }
+    (0..k as u128).fold(1u128, |ways, i| {
+        ways.saturating_mul(n as u128 - i) / (i + 1)
+    })
+}
+
+// The number of cards dealt to each group in turn: the rest of the hero's hand, the rest of
+// the board, then every opponent hand
+fn groups(deal: &Deal) -> Vec<usize> {
+    let (missing_hand, missing_board) = deal.missing_cards();
+    [missing_hand, missing_board]
+        .into_iter()
+        .chain(iter::repeat_n(deal.hole_cards, deal.num_players - 1))
+        .filter(|&size| size > 0)
+        .collect()
+}
+
+// Counts the distinct ways `deal` can be completed, where opponents are told apart by seat
+pub(crate) fn deal_count(deal: &Deal) -> u128 {
+    let mut left = deal.unknown_cards().len();
+    groups(deal).into_iter().fold(1, |ways, size| {
+        let group_ways = choose(left, size);
+        left = left.saturating_sub(size);
+        ways.saturating_mul(group_ways)
+    })
+}
+
+// Like `run_deals`, but calls `score` once for every way to complete `deal` instead of for
+// random ones, so the totals give exact rates over `deal_count` deals
+pub(crate) fn enumerate_deals<const N: usize, F>(deal: Deal, score: F) -> [u128; N]
+where
+    F: Fn(&[Card], &[Card], &[Card]) -> [u64; N] + Sync,
+{
+    let deck = deal.unknown_cards();
+    let groups = groups(&deal);
+    let (missing_hand, missing_board) = deal.missing_cards();
+    // Bit `index` is set for each card of `deck` still to deal; a deck holds at most 56
+    let all_cards = (1 << deck.len()) - 1;
+
+    // Every way to deal the first group is walked on its own thread
+    let (first, rest) = match groups.split_first() {
+        Some((&first, rest)) => (first, rest),
+        None => (0, &[][..]),
+    };
+    let mut starts = Vec::new();
+    deal_groups(
+        &deck,
+        all_cards,
+        &[],
+        first,
+        0,
+        &mut Vec::new(),
+        &mut |dealt, unused| starts.push((dealt.to_vec(), unused)),
+    );
+
+    starts
+        .into_par_iter()
+        .map(|(mut dealt, unused)| {
+            let mut full_hand = deal.hand.to_vec();
+            let mut full_board = deal.board.to_vec();
+            let mut totals = [0; N];
+            deal_groups(&deck, unused, rest, 0, 0, &mut dealt, &mut |dealt, _| {
+                let (hand_cards, dealt) = dealt.split_at(missing_hand);
+                let (runout, opponents) = dealt.split_at(missing_board);
+                full_hand.truncate(deal.hand.len());
+                full_hand.extend_from_slice(hand_cards);
+                full_board.truncate(deal.board.len());
+                full_board.extend_from_slice(runout);
+                totals = add_totals(totals, score(&full_hand, &full_board, opponents));
+            });
+            totals
+        })
+        .reduce(|| [0; N], add_totals)
+}
+
+// Deals `left` more cards of the current group, in increasing deck order from index `from`
+// so each combination comes up once, then every later group from the cards still `unused`,
+// and calls `visit` with each complete deal
+fn deal_groups(
+    deck: &[Card],
+    unused: u64,
+    groups: &[usize],
+    left: usize,
+    from: usize,
+    dealt: &mut Vec<Card>,
+    visit: &mut dyn FnMut(&[Card], u64),
+) {
+    if left == 0 {
+        match groups.split_first() {
+            Some((&size, rest)) => deal_groups(deck, unused, rest, size, 0, dealt, visit),
+            None => visit(dealt, unused),
+        }
+        return;
+    }
+    for index in from..deck.len() {
+        if unused & (1 << index) != 0 {
+            dealt.push(deck[index]);
+            deal_groups(
+                deck,
+                unused & !(1 << index),
+                groups,
+                left - 1,
+                index + 1,
+                dealt,
+                visit,
+            );
+            dealt.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    #[test]
+    fn test_choose() {
+        assert_eq!(choose(52, 5), 2598960);
+        assert_eq!(choose(4, 0), 1);
+        assert_eq!(choose(2, 3), 0);
+    }
+
+    #[test]
+    fn test_deal_count() {
+        let hand = parse_cards("Ah Ad");
+        let turn = parse_cards("As Kh Kd 7c");
+        // 46 rivers, then 990 hands for the opponent from the 45 cards left
+        assert_eq!(deal_count(&Deal::new(2, &hand, &turn, 2)), 46 * 990);
+        let river = parse_cards("As Kh Kd 7c 2s");
+        assert_eq!(deal_count(&Deal::new(2, &hand, &river, 3)), 990 * 903);
+    }
+
+    #[test]
+    fn test_every_deal_is_visited_once() {
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("As Kh Kd 7c");
+        let deal = Deal::new(2, &hand, &board, 2);
+        let count = deal_count(&deal);
+        let [deals, distinct] = enumerate_deals(deal, |hand, board, opponents| {
+            let cards: crate::CardSet = hand.iter().chain(board).chain(opponents).collect();
+            [1, (cards.len() == 9) as u64]
+        });
+        assert_eq!(deals, count);
+        assert_eq!(distinct, deals);
+    }
+}
@@ -0,0 +1,1131 @@
+use std::{cmp::max, collections::HashMap};
+
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::lookup::{evaluate_set, HandStrength};
+
+/// The category and tie-breaking values of a poker hand.
+///
+/// Variants are ordered from weakest to strongest, so two ranks can be compared directly.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub enum HandRank {
+    // HighCard has 5 different values, sorted from highest to lowest
+    HighCard(u8, u8, u8, u8, u8),
+    // OnePair has 1 pair and 3 kickers, sorted from highest to lowest
+    OnePair(u8, u8, u8, u8),
+    // TwoPair has 2 pairs and 1 kicker, sorted from highest to lowest
+    TwoPair(u8, u8, u8),
+    // ThreeOfAKind has 1 triple and 2 kickers, sorted from highest to lowest
+    ThreeOfAKind(u8, u8, u8),
+    // Straight has 1 high card
+    Straight(u8),
+    // Flush has 5 different values with the same suit, sorted from highest to lowest
+    Flush(u8, u8, u8, u8, u8),
+    // FullHouse has 1 triple and 1 pair
+    FullHouse(u8, u8),
+    // FourOfAKind has 1 quadruple and 1 kicker
+    FourOfAKind(u8, u8),
+    // StraightFlush has 1 high card, all cards with the same suit
+    StraightFlush(u8),
+    // RoyalFlush is a special case of StraightFlush, with the highest card being Ace
+    RoyalFlush,
+    // FiveOfAKind has 5 cards of one value, which takes wild cards
+    FiveOfAKind(u8),
+}
+
+/// Evaluates the best poker hand that can be made from `hand` and `board` together.
+///
+/// The hand and board together must hold between 5 and 7 cards, so the same call ranks
+/// a flop, a turn or a full river.
+///
+/// # Panics
+///
+/// Panics if fewer than 5 or more than 7 cards are given.
+pub fn evaluate_hand(hand: &[Card], board: &[Card]) -> HandRank {
+    let mut all_cards = hand.to_vec();
+    all_cards.extend_from_slice(board);
+    assert!(
+        (5..=7).contains(&all_cards.len()),
+        "Invalid card count: expected 5 to 7 cards, found {}",
+        all_cards.len()
+    );
+    all_cards.sort_by_key(|card| std::cmp::Reverse(card.value));
+
+    let (fives, fours, threes, pairs, singles) = check_multiples(&all_cards);
+
+    // Only cards that stand in for others, such as wild cards, can repeat a value five times
+    // or hold a flush together with four of a kind or a full house
+    if let Some(five_value) = fives {
+        return HandRank::FiveOfAKind(five_value);
+    }
+
+    let flush_cards = check_flush(&all_cards);
+    if let Some(straight_values) = flush_cards.as_deref().and_then(check_straight) {
+        return if straight_values.contains(&14) {
+            HandRank::RoyalFlush
+        } else {
+            HandRank::StraightFlush(*straight_values.iter().max().unwrap())
+        };
+    }
+
+    if let Some(four_value) = fours {
+        let kicker = std::cmp::max(
+            threes.first().cloned().unwrap_or(0),
+            std::cmp::max(
+                pairs.first().cloned().unwrap_or(0),
+                singles.first().cloned().unwrap_or(0)
+            )
+        );
+        return HandRank::FourOfAKind(four_value, kicker);
+    } else if threes.len() == 2 || (threes.len() == 1 && !pairs.is_empty()) {
+        let (triple, pair) = if threes.len() == 2 {
+            (threes[0], threes[1])
+        } else {
+            (threes[0], pairs[0])
+        };
+        return HandRank::FullHouse(triple, pair);
+    }
+
+    if let Some(flush_cards) = flush_cards {
+        return HandRank::Flush(flush_cards[0].value, flush_cards[1].value, flush_cards[2].value, flush_cards[3].value, flush_cards[4].value);
+    }
+
+    // A straight must be checked before three of a kind, since seven cards can hold both
+    if let Some(straight_values) = check_straight(&all_cards) {
+        return HandRank::Straight(*straight_values.iter().max().unwrap());
+    }
+
+    if threes.len() == 1 {
+        return HandRank::ThreeOfAKind(threes[0], singles[0], singles[1]);
+    }
+
+    match pairs.len() {
+        // With three pairs the lowest pair can play as the kicker
+        3 => HandRank::TwoPair(pairs[0], pairs[1], max(pairs[2], singles.first().cloned().unwrap_or(0))),
+        2 => HandRank::TwoPair(pairs[0], pairs[1], singles[0]),
+        1 => HandRank::OnePair(pairs[0], singles[0], singles[1], singles[2]),
+        _ => HandRank::HighCard(singles[0], singles[1], singles[2], singles[3], singles[4]),
+    }
+}
+
+/// The best five card hand found by [`evaluate_best_hand`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BestHand {
+    pub rank: HandRank,
+    // The five cards that make the hand, most significant first
+    pub cards: [Card; 5],
+}
+
+/// Like [`evaluate_hand`], but also returns the five cards that make the best hand.
+///
+/// When several choices of cards give the same hand, board cards are preferred over hole
+/// cards, so a hole card only appears if it actually plays. The cards are ordered by
+/// importance, for example the triple before the pair of a full house, and the ace last in
+/// a five high straight.
+///
+/// # Panics
+///
+/// Panics if fewer than 5 or more than 7 cards are given.
+pub fn evaluate_best_hand(hand: &[Card], board: &[Card]) -> BestHand {
+    let all_cards: Vec<Card> = hand.iter().chain(board).copied().collect();
+    assert!(
+        (5..=7).contains(&all_cards.len()),
+        "Invalid card count: expected 5 to 7 cards, found {}",
+        all_cards.len()
+    );
+
+    // Hole cards come first in all_cards, so they are the low bits of each subset
+    let hole_mask = (1u32 << hand.len()) - 1;
+    let mut best: Option<(HandStrength, u32, [Card; 5])> = None;
+
+    for subset in 0u32..(1 << all_cards.len()) {
+        if subset.count_ones() != 5 {
+            continue;
+        }
+        let mut cards = [all_cards[0]; 5];
+        for (slot, index) in (0..all_cards.len()).filter(|i| subset & (1 << i) != 0).enumerate() {
+            cards[slot] = all_cards[index];
+        }
+        let strength = evaluate_set(cards.iter().collect::<CardSet>());
+        let hole_cards = (subset & hole_mask).count_ones();
+        let better = match best {
+            None => true,
+            Some((best_strength, best_hole_cards, _)) => {
+                strength > best_strength || (strength == best_strength && hole_cards < best_hole_cards)
+            }
+        };
+        if better {
+            best = Some((strength, hole_cards, cards));
+        }
+    }
+
+    let (strength, _, cards) = best.unwrap();
+    order_best_hand(strength.rank(), cards)
+}
+
+// Orders the five cards of a hand by importance: larger groups first, then higher values,
+// with the ace at the end of a five high straight
+pub(crate) fn order_best_hand(rank: HandRank, mut cards: [Card; 5]) -> BestHand {
+    // The ace plays low in A-2-3-4-5, and in the Short Deck straight A-6-7-8-9
+    let wheel = matches!(
+        rank,
+        HandRank::Straight(5 | 9) | HandRank::StraightFlush(5 | 9)
+    );
+    let counts: HashMap<u8, usize> = cards.iter().fold(HashMap::new(), |mut counts, card| {
+        *counts.entry(card.value).or_insert(0) += 1;
+        counts
+    });
+    cards.sort_by_key(|card| {
+        let value = if wheel && card.value == 14 { 1 } else { card.value };
+        std::cmp::Reverse((counts[&card.value], value))
+    });
+
+    BestHand { rank, cards }
+}
+
+/// Compares two hand ranks, returning 1 if `hand1` wins, -1 if `hand2` wins and 0 on a tie.
+pub fn compare_hands(hand1: HandRank, hand2: HandRank) -> i32 {
+    use HandRank::*;
+
+    match (hand1, hand2) {
+        (RoyalFlush, RoyalFlush) => 0,
+        (StraightFlush(high_card1), StraightFlush(high_card2)) => {
+            high_card1.cmp(&high_card2) as i32
+        }
+        (FourOfAKind(four1, kicker1), FourOfAKind(four2, kicker2)) => {
+            let cards1 = vec![four1, kicker1];
+            let cards2 = vec![four2, kicker2];
+            compare_cards(&cards1, &cards2)
+        }
+        (FullHouse(three1, pair1), FullHouse(three2, pair2)) => {
+            let cards1 = vec![three1, pair1];
+            let cards2 = vec![three2, pair2];
+            compare_cards(&cards1, &cards2)
+        }
+        (Flush(a1, b1, c1, d1, e1), Flush(a2, b2, c2, d2, e2)) => {
+            let cards1 = vec![a1, b1, c1, d1, e1];
+            let cards2 = vec![a2, b2, c2, d2, e2];
+            compare_cards(&cards1, &cards2)
+        }
+        (Straight(high_card1), Straight(high_card2)) => high_card1.cmp(&high_card2) as i32,
+        (
+            ThreeOfAKind(three1, kicker1_1, kicker1_2),
+            ThreeOfAKind(three2, kicker2_1, kicker2_2),
+        ) => {
+            let cards1 = vec![three1, kicker1_1, kicker1_2];
+            let cards2 = vec![three2, kicker2_1, kicker2_2];
+            compare_cards(&cards1, &cards2)
+        }
+        (TwoPair(high_pair1, low_pair1, kicker1), TwoPair(high_pair2, low_pair2, kicker2)) => {
+            let cards1 = vec![high_pair1, low_pair1, kicker1];
+            let cards2 = vec![high_pair2, low_pair2, kicker2];
+            compare_cards(&cards1, &cards2)
+        }
+        (
+            OnePair(pair1, kicker1_1, kicker1_2, kicker1_3),
+            OnePair(pair2, kicker2_1, kicker2_2, kicker2_3),
+        ) => {
+            let cards1 = vec![pair1, kicker1_1, kicker1_2, kicker1_3];
+            let cards2 = vec![pair2, kicker2_1, kicker2_2, kicker2_3];
+            compare_cards(&cards1, &cards2)
+        }
+        (HighCard(a1, b1, c1, d1, e1), HighCard(a2, b2, c2, d2, e2)) => {
+            let cards1 = vec![a1, b1, c1, d1, e1];
+            let cards2 = vec![a2, b2, c2, d2, e2];
+            compare_cards(&cards1, &cards2)
+        }
+        (_, _) => hand1.partial_cmp(&hand2).unwrap() as i32,
+    }
+}
+
+fn compare_cards(cards1: &[u8], cards2: &[u8]) -> i32 {
+    let mut iter1 = cards1.iter();
+    let mut iter2 = cards2.iter();
+
+    loop {
+        match (iter1.next(), iter2.next()) {
+            (Some(card1), Some(card2)) => {
+                let cmp = card1.cmp(card2);
+                if cmp != std::cmp::Ordering::Equal {
+                    return cmp as i32;
+                }
+            }
+            (None, None) => return 0,
+            (Some(_), None) => return 1,
+            (None, Some(_)) => return -1,
+        }
+    }
+}
+
+fn check_flush(cards: &[Card]) -> Option<Vec<Card>> {
+    let mut suits = HashMap::new();
+
+    for card in cards {
+        *suits.entry(card.suit).or_insert(0) += 1;
+    }
+
+    let flush_suit = suits.into_iter().find(|(_, count)| *count >= 5)?;
+    let mut flush_cards: Vec<Card> = cards
+        .iter()
+        .filter(|card| card.suit == flush_suit.0)
+        .cloned()
+        .collect();
+
+    if flush_cards.len() >= 5 {
+        flush_cards.sort_by_key(|card| std::cmp::Reverse(card.value));
+        Some(flush_cards)
+    } else {
+        None
+    }
+}
+
+fn check_straight(cards: &[Card]) -> Option<Vec<u8>> {
+    if cards.len() < 5 {
+        return None;
+    }
+
+    let mut values = cards.iter().map(|card| card.value).collect::<Vec<u8>>();
+    values.sort_unstable();
+    values.dedup();
+
+    let has_high_ace = values.contains(&14);
+    if has_high_ace {
+        values.insert(0, 1);
+    }
+
+    let mut consecutive_count = 1;
+    let mut straight_values = Vec::new();
+
+    for i in 0..values.len() - 1 {
+        if values[i] + 1 == values[i + 1] {
+            consecutive_count += 1;
+            if consecutive_count >= 5 {
+                straight_values.push(values[i + 1]);
+            }
+        } else {
+            consecutive_count = 1;
+        }
+    }
+
+    if !straight_values.is_empty() {
+        straight_values.sort_by(|a, b| b.cmp(a));
+        Some(straight_values)
+    } else {
+        None
+    }
+}
+
+// The highest value held five times and four times, then the values held three times, twice
+// and once, each from highest to lowest
+type Multiples = (Option<u8>, Option<u8>, Vec<u8>, Vec<u8>, Vec<u8>);
+
+fn check_multiples(cards: &[Card]) -> Multiples {
+    let mut counts = HashMap::new();
+
+    for card in cards {
+        *counts.entry(card.value).or_insert(0) += 1;
+    }
+
+    let mut five = None;
+    let mut four = None;
+    let mut three = Vec::new();
+    let mut pairs = Vec::new();
+    let mut singles = Vec::new();
+
+    for (value, count) in counts {
+        match count {
+            4 => four = max(four, Some(value)),
+            3 => three.push(value),
+            2 => pairs.push(value),
+            1 => singles.push(value),
+            _ => five = max(five, Some(value)),
+        }
+    }
+
+    three.sort_unstable_by(|a, b| b.cmp(a));
+    pairs.sort_unstable_by(|a, b| b.cmp(a));
+    singles.sort_unstable_by(|a, b| b.cmp(a));
+
+    (five, four, three, pairs, singles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_straight_exists() {
+        let cards = vec![
+            Card { value: 2, suit: 0 },
+            Card { value: 3, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 6, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![6]));
+    }
+
+    #[test]
+    fn test_no_straight() {
+        let cards = vec![
+            Card { value: 2, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 6, suit: 0 },
+            Card { value: 8, suit: 0 },
+            Card { value: 10, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), None);
+    }
+
+    #[test]
+    fn test_straight_with_duplicates() {
+        let cards = vec![
+            Card { value: 3, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 6, suit: 0 },
+            Card { value: 7, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![7]));
+    }
+
+    #[test]
+    fn test_straight_with_ace_high() {
+        let cards = vec![
+            Card { value: 10, suit: 0 },
+            Card { value: 11, suit: 0 },
+            Card { value: 12, suit: 0 },
+            Card { value: 13, suit: 0 },
+            Card { value: 14, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![14]));
+    }
+
+    #[test]
+    fn test_straight_with_ace_low() {
+        let cards = vec![
+            Card { value: 2, suit: 0 },
+            Card { value: 3, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 14, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![5]));
+    }
+
+    #[test]
+    fn test_straight_seven_cards() {
+        let cards = vec![
+            Card { value: 2, suit: 0 },
+            Card { value: 3, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 6, suit: 0 },
+            Card { value: 7, suit: 0 },
+            Card { value: 8, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![8, 7, 6]));
+    }
+
+    #[test]
+    fn test_straight_seven_non_continuous_cards() {
+        let cards = vec![
+            Card { value: 2, suit: 0 },
+            Card { value: 3, suit: 0 },
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 6, suit: 0 },
+            Card { value: 8, suit: 0 },
+            Card { value: 9, suit: 0 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![6]));
+    }
+
+    #[test]
+    fn test_no_pairs_threes_or_fours() {
+        let cards = [
+            Card { value: 2, suit: 0 },
+            Card { value: 4, suit: 1 },
+            Card { value: 6, suit: 2 },
+            Card { value: 8, suit: 3 },
+            Card { value: 10, suit: 0 },
+        ];
+        assert_eq!(
+            check_multiples(&cards),
+            (None, None, vec![], vec![], vec![10, 8, 6, 4, 2])
+        );
+    }
+
+    #[test]
+    fn test_one_pair() {
+        let cards = [
+            Card { value: 3, suit: 0 },
+            Card { value: 3, suit: 1 },
+            Card { value: 6, suit: 2 },
+            Card { value: 8, suit: 3 },
+            Card { value: 10, suit: 0 },
+        ];
+        assert_eq!(
+            check_multiples(&cards),
+            (None, None, vec![], vec![3], vec![10, 8, 6])
+        );
+    }
+
+    #[test]
+    fn test_two_pairs() {
+        let cards = [
+            Card { value: 5, suit: 0 },
+            Card { value: 5, suit: 1 },
+            Card { value: 6, suit: 2 },
+            Card { value: 6, suit: 3 },
+            Card { value: 10, suit: 0 },
+        ];
+        assert_eq!(
+            check_multiples(&cards),
+            (None, None, vec![], vec![6, 5], vec![10])
+        );
+    }
+
+    #[test]
+    fn test_three_pairs() {
+        let cards = [
+            Card { value: 7, suit: 0 },
+            Card { value: 7, suit: 1 },
+            Card { value: 5, suit: 2 },
+            Card { value: 5, suit: 3 },
+            Card { value: 6, suit: 0 },
+            Card { value: 6, suit: 1 },
+        ];
+        assert_eq!(
+            check_multiples(&cards),
+            (None, None, vec![], vec![7, 6, 5], vec![])
+        );
+    }
+
+    #[test]
+    fn test_three_of_a_kind() {
+        let cards = [
+            Card { value: 7, suit: 0 },
+            Card { value: 7, suit: 1 },
+            Card { value: 7, suit: 2 },
+            Card { value: 8, suit: 3 },
+            Card { value: 10, suit: 0 },
+        ];
+        assert_eq!(
+            check_multiples(&cards),
+            (None, None, vec![7], vec![], vec![10, 8])
+        );
+    }
+
+    #[test]
+    fn test_double_three_of_a_kind() {
+        let cards = [
+            Card { value: 7, suit: 0 },
+            Card { value: 8, suit: 1 },
+            Card { value: 7, suit: 2 },
+            Card { value: 8, suit: 0 },
+            Card { value: 7, suit: 1 },
+            Card { value: 8, suit: 2 },
+        ];
+        assert_eq!(check_multiples(&cards), (None, None, vec![8, 7], vec![], vec![]));
+    }
+
+    #[test]
+    fn test_four_of_a_kind() {
+        let cards = [
+            Card { value: 9, suit: 0 },
+            Card { value: 9, suit: 1 },
+            Card { value: 9, suit: 2 },
+            Card { value: 9, suit: 3 },
+            Card { value: 10, suit: 0 },
+        ];
+        assert_eq!(check_multiples(&cards), (None, Some(9), vec![], vec![], vec![10]));
+    }
+
+    #[test]
+    fn test_a_pair_and_three_of_a_kind() {
+        let cards = [
+            Card { value: 2, suit: 0 },
+            Card { value: 2, suit: 1 },
+            Card { value: 2, suit: 2 },
+            Card { value: 3, suit: 3 },
+            Card { value: 3, suit: 0 },
+        ];
+        assert_eq!(check_multiples(&cards), (None, None, vec![2], vec![3], vec![]));
+    }
+
+    #[test]
+    fn test_full_house_1() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 3, suit: 3 },
+            Card { value: 3, suit: 0 },
+            Card { value: 3, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(3, 2));
+    }
+
+    #[test]
+    fn test_full_house_2() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 3, suit: 0 },
+            Card { value: 3, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(2, 3));
+    }
+
+    #[test]
+    fn test_full_house_3() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 3, suit: 0 },
+            Card { value: 3, suit: 1 },
+            Card { value: 4, suit: 0 },
+            Card { value: 4, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(2, 4));
+    }
+
+    #[test]
+    fn test_straight_flush() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 3, suit: 0 }];
+        let boards = [
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 6, suit: 1 },
+            Card { value: 8, suit: 0 },
+        ];
+        assert_ne!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(6));
+    }
+
+    #[test]
+    fn test_straight_flush_2() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 3, suit: 0 }];
+        let boards = [
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 6, suit: 0 },
+            Card { value: 8, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(6));
+    }
+
+    #[test]
+    fn test_compare_hands_three_1() {
+        let hand1 = HandRank::ThreeOfAKind(10, 9, 8);
+        let hand2 = HandRank::ThreeOfAKind(10, 9, 8);
+        assert_eq!(compare_hands(hand1, hand2), 0);
+    }
+
+    #[test]
+    fn test_compare_hands_three_2() {
+        let hand1 = HandRank::ThreeOfAKind(10, 9, 6);
+        let hand2 = HandRank::ThreeOfAKind(10, 8, 6);
+        assert_eq!(compare_hands(hand1, hand2), 1);
+    }
+
+    #[test]
+    fn test_compare_hands_three_3() {
+        let hand1 = HandRank::ThreeOfAKind(10, 9, 8);
+        let hand2 = HandRank::ThreeOfAKind(10, 9, 7);
+        assert_eq!(compare_hands(hand1, hand2), 1);
+    }
+
+    #[test]
+    fn test_compare_hands_flush_1() {
+        let hand1 = HandRank::Flush(10, 9, 8, 7, 4);
+        let hand2 = HandRank::Flush(10, 9, 8, 7, 5);
+        assert_eq!(compare_hands(hand1, hand2), -1);
+    }
+
+    #[test]
+    fn test_compare_hands_two_pairs_1() {
+        let hand1 = HandRank::TwoPair(10, 9, 8);
+        let hand2 = HandRank::TwoPair(10, 9, 7);
+        assert_eq!(compare_hands(hand1, hand2), 1);
+    }
+
+    #[test]
+    fn test_compare_hands_one_pairs_1() {
+        let hand1 = HandRank::OnePair(10, 9, 8, 7);
+        let hand2 = HandRank::OnePair(10, 9, 8, 6);
+        assert_eq!(compare_hands(hand1, hand2), 1);
+    }
+
+    #[test]
+    fn test_compare_hands_tie_1() {
+        let hand1 = HandRank::Flush(10, 9, 8, 7, 4);
+        let hand2 = HandRank::Flush(10, 9, 8, 7, 4);
+        assert_eq!(compare_hands(hand1, hand2), 0);
+    }
+
+    #[test]
+    fn test_compare_hands_tie_2() {
+        let hand1 = HandRank::HighCard(10, 9, 8, 7, 4);
+        let hand2 = HandRank::HighCard(10, 9, 8, 7, 4);
+        assert_eq!(compare_hands(hand1, hand2), 0);
+    }
+
+    #[test]
+    fn test_four_of_a_kind_1() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 2, suit: 3 },
+            Card { value: 9, suit: 0 },
+            Card { value: 8, suit: 0 },
+            Card { value: 8, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 9));
+    }
+
+    #[test]
+    fn test_four_of_a_kind_2() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 2, suit: 3 },
+            Card { value: 6, suit: 0 },
+            Card { value: 8, suit: 0 },
+            Card { value: 8, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 8));
+    }
+
+    #[test]
+    fn test_four_of_a_kind_3() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 2, suit: 3 },
+            Card { value: 7, suit: 0 },
+            Card { value: 7, suit: 1 },
+            Card { value: 7, suit: 2 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 7));
+    }
+
+    #[test]
+    fn test_four_of_a_kind_4() {
+        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
+        let boards = [
+            Card { value: 2, suit: 2 },
+            Card { value: 2, suit: 3 },
+            Card { value: 6, suit: 0 },
+            Card { value: 10, suit: 1 },
+            Card { value: 5, suit: 2 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 10));
+    }
+
+    #[test]
+    fn test_straight_ace_both_high_and_low() {
+        let cards = vec![
+            Card { value: 10, suit: 0 },
+            Card { value: 11, suit: 1 },
+            Card { value: 12, suit: 2 },
+            Card { value: 13, suit: 3 },
+            Card { value: 14, suit: 0 },
+            Card { value: 2, suit: 1 },
+        ];
+        assert_eq!(check_straight(&cards), Some(vec![14]));
+    }
+
+    #[test]
+    fn test_two_three_of_a_kinds() {
+        let cards = [
+            Card { value: 6, suit: 0 },
+            Card { value: 6, suit: 1 },
+            Card { value: 6, suit: 2 },
+            Card { value: 8, suit: 3 },
+            Card { value: 8, suit: 0 },
+            Card { value: 8, suit: 1 },
+        ];
+        assert_eq!(check_multiples(&cards), (None, None, vec![8, 6], vec![], vec![]));
+    }
+
+    #[test]
+    fn test_straight_and_one_pair() {
+        let cards = [
+            Card { value: 2, suit: 0 },
+            Card { value: 3, suit: 1 },
+            Card { value: 4, suit: 2 },
+            Card { value: 5, suit: 3 },
+            Card { value: 6, suit: 0 },
+            Card { value: 6, suit: 2 },
+        ];
+        assert_eq!(
+            check_multiples(&cards),
+            (None, None, vec![], vec![6], vec![5, 4, 3, 2])
+        );
+    }
+
+    #[test]
+    fn test_full_house_tiebreaker() {
+        let cards1 = [Card { value: 2, suit: 0 }, Card { value: 5, suit: 1 }];
+        let cards2 = [Card { value: 3, suit: 0 }, Card { value: 4, suit: 0 }];
+
+        let boards = [
+            Card { value: 2, suit: 1 },
+            Card { value: 2, suit: 2 },
+            Card { value: 3, suit: 1 },
+            Card { value: 3, suit: 2 },
+        ];
+
+        assert_eq!(evaluate_hand(&cards1, &boards), HandRank::FullHouse(2, 3));
+        assert_eq!(evaluate_hand(&cards2, &boards), HandRank::FullHouse(3, 2));
+
+        assert_eq!(
+            compare_hands(
+                evaluate_hand(&cards2, &boards),
+                evaluate_hand(&cards1, &boards)
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn test_straight_flush_ace_low() {
+        let cards = [Card { value: 14, suit: 0 }, Card { value: 3, suit: 0 }];
+        let boards = [
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 2, suit: 0 },
+            Card { value: 8, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(5));
+    }
+
+    #[test]
+    fn test_straight_ace_low() {
+        let cards = [Card { value: 14, suit: 0 }, Card { value: 3, suit: 0 }];
+        let boards = [
+            Card { value: 4, suit: 0 },
+            Card { value: 5, suit: 0 },
+            Card { value: 2, suit: 1 },
+            Card { value: 8, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::Straight(5));
+    }
+
+    #[test]
+    fn test_straight_flush_1() {
+        let cards = [Card { value: 8, suit: 0 }, Card { value: 8, suit: 1 }];
+        let boards = [
+            Card { value: 9, suit: 0 },
+            Card { value: 10, suit: 0 },
+            Card { value: 11, suit: 0 },
+            Card { value: 12, suit: 0 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(12));
+    }
+
+    #[test]
+    fn test_full_house_4() {
+        let cards = [Card { value: 5, suit: 0 }, Card { value: 5, suit: 1 }];
+        let boards = [
+            Card { value: 5, suit: 2 },
+            Card { value: 9, suit: 3 },
+            Card { value: 9, suit: 0 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(5, 9));
+    }
+
+    #[test]
+    fn test_full_house_tiebreaker_2() {
+        let cards = [Card { value: 7, suit: 0 }, Card { value: 7, suit: 1 }];
+
+        let boards = [
+            Card { value: 7, suit: 1 },
+            Card { value: 5, suit: 2 },
+            Card { value: 5, suit: 3 },
+            Card { value: 8, suit: 0 },
+            Card { value: 4, suit: 0 },
+        ];
+
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(7, 5));
+    }
+
+    #[test]
+    fn test_high_card_multiple_kickers() {
+        let cards = [Card { value: 3, suit: 0 }, Card { value: 5, suit: 1 }];
+        let boards = [
+            Card { value: 6, suit: 3 },
+            Card { value: 7, suit: 0 },
+            Card { value: 9, suit: 0 },
+            Card { value: 11, suit: 2 },
+            Card { value: 13, suit: 1 },
+        ];
+        assert_eq!(
+            evaluate_hand(&cards, &boards),
+            HandRank::HighCard(13, 11, 9, 7, 6)
+        );
+    }
+
+    #[test]
+    fn test_flush_1() {
+        let cards = [Card { value: 5, suit: 1 }, Card { value: 8, suit: 1 }];
+        let boards = [
+            Card { value: 8, suit: 2 },
+            Card { value: 14, suit: 0 },
+            Card { value: 4, suit: 1 },
+            Card { value: 14, suit: 1 },
+            Card { value: 6, suit: 1 },
+        ];
+        assert_eq!(
+            evaluate_hand(&cards, &boards),
+            HandRank::Flush(14, 8, 6, 5, 4)
+        );
+    }
+
+    #[test]
+    fn test_straight_flush_3() {
+        let cards = [Card { value: 8, suit: 1 }, Card { value: 8, suit: 2 }];
+        let boards = [
+            Card { value: 10, suit: 1 },
+            Card { value: 11, suit: 1 },
+            Card { value: 12, suit: 1 },
+            Card { value: 9, suit: 1 },
+            Card { value: 13, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(13));
+    }
+
+    #[test]
+    fn test_royal_flush() {
+        let cards = [Card { value: 9, suit: 1 }, Card { value: 9, suit: 2 }];
+        let boards = [
+            Card { value: 10, suit: 1 },
+            Card { value: 11, suit: 1 },
+            Card { value: 12, suit: 1 },
+            Card { value: 13, suit: 1 },
+            Card { value: 14, suit: 0 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(13));
+    }
+
+    #[test]
+    fn test_royal_flush_2() {
+        let cards = [Card { value: 9, suit: 1 }, Card { value: 9, suit: 2 }];
+        let boards = [
+            Card { value: 10, suit: 1 },
+            Card { value: 11, suit: 1 },
+            Card { value: 12, suit: 1 },
+            Card { value: 13, suit: 1 },
+            Card { value: 14, suit: 1 },
+        ];
+        assert_eq!(evaluate_hand(&cards, &boards), HandRank::RoyalFlush);
+    }
+
+    fn category(rank: HandRank) -> usize {
+        match rank {
+            HandRank::HighCard(..) => 0,
+            HandRank::OnePair(..) => 1,
+            HandRank::TwoPair(..) => 2,
+            HandRank::ThreeOfAKind(..) => 3,
+            HandRank::Straight(_) => 4,
+            HandRank::Flush(..) => 5,
+            HandRank::FullHouse(..) => 6,
+            HandRank::FourOfAKind(..) => 7,
+            HandRank::StraightFlush(_) => 8,
+            HandRank::RoyalFlush => 9,
+            HandRank::FiveOfAKind(_) => 10,
+        }
+    }
+
+    // Brute force reference: the best rank among every five card subset
+    fn best_of_five_subsets(cards: &[Card]) -> HandRank {
+        let n = cards.len();
+        let mut best = None;
+        for skip in 0u32..(1 << n) {
+            if n - skip.count_ones() as usize != 5 {
+                continue;
+            }
+            let subset: Vec<Card> = (0..n)
+                .filter(|i| skip & (1 << i) == 0)
+                .map(|i| cards[i])
+                .collect();
+            let rank = evaluate_hand(&subset[..2], &subset[2..]);
+            best = Some(best.map_or(rank, |b: HandRank| b.max(rank)));
+        }
+        best.unwrap()
+    }
+
+    // Walks all 2,598,960 five card hands, which is only quick in release builds
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn test_five_card_category_counts() {
+        let deck = crate::deck::create_deck();
+        let mut counts = [0u32; 10];
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let hand = [deck[a], deck[b]];
+                            let board = [deck[c], deck[d], deck[e]];
+                            counts[category(evaluate_hand(&hand, &board))] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            counts,
+            [1302540, 1098240, 123552, 54912, 10200, 5108, 3744, 624, 36, 4]
+        );
+    }
+
+    fn assert_matches_best_subset(card_count: usize) {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(card_count as u64);
+        let mut deck = crate::deck::create_deck();
+        for _ in 0..2000 {
+            deck.shuffle(&mut rng);
+            let cards = &deck[..card_count];
+            assert_eq!(
+                evaluate_hand(&cards[..2], &cards[2..]),
+                best_of_five_subsets(cards),
+                "{:?}",
+                cards
+            );
+        }
+    }
+
+    #[test]
+    fn test_six_cards_match_best_subset() {
+        assert_matches_best_subset(6);
+    }
+
+    #[test]
+    fn test_seven_cards_match_best_subset() {
+        assert_matches_best_subset(7);
+    }
+
+    fn cards(input: &str) -> Vec<Card> {
+        crate::card::parse_cards(input)
+    }
+
+    #[test]
+    fn test_every_category_with_five_cards() {
+        let cases = [
+            ("Ah Kd", "9c 7s 3h", HandRank::HighCard(14, 13, 9, 7, 3)),
+            ("Ah Ad", "9c 7s 3h", HandRank::OnePair(14, 9, 7, 3)),
+            ("Ah Ad", "9c 9s 3h", HandRank::TwoPair(14, 9, 3)),
+            ("Ah Ad", "As 9s 3h", HandRank::ThreeOfAKind(14, 9, 3)),
+            ("Ah 2d", "3c 4s 5h", HandRank::Straight(5)),
+            ("9h Td", "Jc Qs Kh", HandRank::Straight(13)),
+            ("Ah Jh", "9h 7h 3h", HandRank::Flush(14, 11, 9, 7, 3)),
+            ("Ah Ad", "As 9s 9h", HandRank::FullHouse(14, 9)),
+            ("Ah Ad", "As Ac 9h", HandRank::FourOfAKind(14, 9)),
+            ("5h 6h", "7h 8h 9h", HandRank::StraightFlush(9)),
+            ("Ah Kh", "Qh Jh Th", HandRank::RoyalFlush),
+        ];
+        for (hand, board, expected) in cases {
+            assert_eq!(evaluate_hand(&cards(hand), &cards(board)), expected, "{} {}", hand, board);
+        }
+    }
+
+    #[test]
+    fn test_every_category_with_six_cards() {
+        let cases = [
+            ("Ah Kd", "9c 7s 3h 2d", HandRank::HighCard(14, 13, 9, 7, 3)),
+            ("Ah Ad", "9c 7s 3h 2d", HandRank::OnePair(14, 9, 7, 3)),
+            ("Ah Ad", "9c 9s 3h 2d", HandRank::TwoPair(14, 9, 3)),
+            ("Ah Ad", "9c 9s 3h 3d", HandRank::TwoPair(14, 9, 3)),
+            ("Ah Ad", "As 9s 3h 2d", HandRank::ThreeOfAKind(14, 9, 3)),
+            ("Ah 2d", "3c 4s 5h 5d", HandRank::Straight(5)),
+            ("5h 6d", "7c 8s 9h Td", HandRank::Straight(10)),
+            ("Ah Jh", "9h 7h 3h 2h", HandRank::Flush(14, 11, 9, 7, 3)),
+            ("Ah Ad", "As 9s 9h 2d", HandRank::FullHouse(14, 9)),
+            ("Ah Ad", "As 9s 9h 9d", HandRank::FullHouse(14, 9)),
+            ("Ah Ad", "As Ac 9h Kd", HandRank::FourOfAKind(14, 13)),
+            ("Ah Ad", "As Ac 9h 9d", HandRank::FourOfAKind(14, 9)),
+            ("5h 6h", "7h 8h 9h Th", HandRank::StraightFlush(10)),
+            ("Ah Kh", "Qh Jh Th 9h", HandRank::RoyalFlush),
+        ];
+        for (hand, board, expected) in cases {
+            assert_eq!(evaluate_hand(&cards(hand), &cards(board)), expected, "{} {}", hand, board);
+        }
+    }
+
+    #[test]
+    fn test_every_category_with_seven_cards() {
+        let cases = [
+            ("Ah Kd", "9c 7s 3h 2d 4c", HandRank::HighCard(14, 13, 9, 7, 4)),
+            ("Ah Ad", "9c 7s 3h 2d 4c", HandRank::OnePair(14, 9, 7, 4)),
+            ("Ah Ad", "9c 9s 3h 2d 4c", HandRank::TwoPair(14, 9, 4)),
+            ("Ah Ad", "9c 9s 3h 3d 2c", HandRank::TwoPair(14, 9, 3)),
+            ("Ah Ad", "9c 9s 3h 3d Kc", HandRank::TwoPair(14, 9, 13)),
+            ("Ah Ad", "As 9s 3h 2d 4c", HandRank::ThreeOfAKind(14, 9, 4)),
+            ("5h 5d", "5c 6s 7h 8d 9c", HandRank::Straight(9)),
+            ("Ah 2d", "3c 4s 5h 5d 5c", HandRank::Straight(5)),
+            ("Ah Jh", "9h 7h 3h 2h Kd", HandRank::Flush(14, 11, 9, 7, 3)),
+            ("Ah Ad", "As 9s 9h 2d 4c", HandRank::FullHouse(14, 9)),
+            ("Ah Ad", "As 9s 9h 9d 2c", HandRank::FullHouse(14, 9)),
+            ("Ah Ad", "As Ac 9h 9d 9c", HandRank::FourOfAKind(14, 9)),
+            ("5h 6h", "7h 8h 9h Th 2c", HandRank::StraightFlush(10)),
+            ("Ah Kh", "Qh Jh Th 9h 8h", HandRank::RoyalFlush),
+        ];
+        for (hand, board, expected) in cases {
+            assert_eq!(evaluate_hand(&cards(hand), &cards(board)), expected, "{} {}", hand, board);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid card count")]
+    fn test_too_few_cards() {
+        evaluate_hand(&cards("Ah Kd"), &cards("9c 7s"));
+    }
+
+    fn best_cards(hand: &str, board: &str) -> String {
+        let best = evaluate_best_hand(&cards(hand), &cards(board));
+        assert_eq!(best.rank, evaluate_hand(&cards(hand), &cards(board)));
+        best.cards.iter().map(|card| card.to_string()).collect::<Vec<_>>().join(" ")
+    }
+
+    #[test]
+    fn test_best_hand_cards() {
+        assert_eq!(best_cards("Ah Ad", "As Kh Kd 7c 2s"), "Ah Ad As Kh Kd");
+        assert_eq!(best_cards("9h 8h", "Th Jh Qh 2c 3d"), "Qh Jh Th 9h 8h");
+        assert_eq!(best_cards("Ac 9d", "2h 3s 4d 5c Kh"), "5c 4d 3s 2h Ac");
+        assert_eq!(best_cards("Ah 7d", "Kc Ks 4h 4d 2c"), "Kc Ks 4h 4d Ah");
+        assert_eq!(best_cards("Qh Qd", "Qc 9s 9h 9d 2c"), "Qh Qd Qc 9s 9h");
+    }
+
+    #[test]
+    fn test_best_hand_board_plays() {
+        // The hero's king ties the board's king, so the board plays
+        assert_eq!(best_cards("Kd 2s", "Ah Ad Ac As Kh"), "Ah Ad Ac As Kh");
+        assert_eq!(best_cards("2c 3d", "Ah Kh Qh Jh Th"), "Ah Kh Qh Jh Th");
+    }
+
+    #[test]
+    fn test_best_hand_kicker_plays() {
+        assert_eq!(best_cards("Ad 3c", "Ah Ks 9d 7c 4h"), "Ad Ah Ks 9d 7c");
+        assert_eq!(best_cards("Kd 3c", "Ah As 9d 7c 4h"), "Ah As Kd 9d 7c");
+    }
+
+    #[test]
+    fn test_repeated_cards() {
+        // Cards can only repeat when wild cards stand in for them
+        assert_eq!(
+            evaluate_hand(&cards("Ah Ah"), &cards("Ad Ac As 7c 2s")),
+            HandRank::FiveOfAKind(14)
+        );
+        assert_eq!(
+            evaluate_hand(&cards("Kh Kh"), &cards("Ks Kd Qh 9h 2h")),
+            HandRank::FourOfAKind(13, 12)
+        );
+        assert_eq!(
+            evaluate_hand(&cards("Kh Kh"), &cards("Ks Qd Qh 9h 2h")),
+            HandRank::FullHouse(13, 12)
+        );
+    }
+}
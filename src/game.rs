@@ -0,0 +1,51 @@
+use crate::card::Card;
+use crate::evaluator::{evaluate_best_hand, BestHand};
+use crate::lookup::{evaluate_strength, HandStrength};
+use crate::low::{best_low_eight, eight_or_better_mask, LowRank};
+use crate::omaha::{evaluate_best_omaha_hand, evaluate_omaha, evaluate_omaha_low};
+
+/// The poker variants the simulator can deal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Game {
+    // Texas Hold'em, 2 hole cards played freely with the board
+    Holdem,
+    // Omaha with 4, 5 or 6 hole cards, using exactly 2 of them with 3 board cards
+    Omaha(usize),
+}
+
+impl Game {
+    /// Number of hole cards dealt to each player.
+    pub fn hole_cards(self) -> usize {
+        match self {
+            Game::Holdem => 2,
+            Game::Omaha(hole_cards) => hole_cards,
+        }
+    }
+
+    /// Evaluates a player's `hole` cards on `board` under the rules of this game.
+    pub fn evaluate(self, hole: &[Card], board: &[Card]) -> HandStrength {
+        match self {
+            Game::Holdem => evaluate_strength(hole, board),
+            Game::Omaha(_) => evaluate_omaha(hole, board),
+        }
+    }
+
+    /// Like [`Game::evaluate`], but also returns the five cards that make the best hand.
+    pub fn best_hand(self, hole: &[Card], board: &[Card]) -> BestHand {
+        match self {
+            Game::Holdem => evaluate_best_hand(hole, board),
+            Game::Omaha(_) => evaluate_best_omaha_hand(hole, board),
+        }
+    }
+
+    /// Evaluates the eight-or-better low of a player's `hole` cards on `board`, for the
+    /// split pot version of this game.
+    pub fn evaluate_low(self, hole: &[Card], board: &[Card]) -> Option<LowRank> {
+        match self {
+            Game::Holdem => {
+                best_low_eight(eight_or_better_mask(hole) | eight_or_better_mask(board))
+            }
+            Game::Omaha(_) => evaluate_omaha_low(hole, board),
+        }
+    }
+}
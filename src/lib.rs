@@ -0,0 +1,77 @@
+//! Poker hand evaluation and equity simulation.
+//!
+//! The crate is split into a few small modules:
+//!
+//! - [`card`]: the [`Card`] type and text parsing.
+//! - [`card_set`]: [`CardSet`], a bitmask of cards for fast set operations.
+//! - [`deck`]: building a deck and removing known cards from it.
+//! - [`draw`]: Five Card Draw discard analysis and deuce-to-seven draw simulation.
+//! - [`evaluator`]: ranking and comparing Texas Hold'em hands.
+//! - [`lookup`]: a faster table-driven evaluator returning a dense [`HandStrength`].
+//! - [`low`]: ace-to-five and deuce-to-seven low hands.
+//! - [`omaha`]: Omaha evaluation, using exactly two hole cards and three board cards.
+//! - [`pineapple`]: Pineapple and Crazy Pineapple, with three hole cards and a discard.
+//! - [`pot_odds`]: pot odds, call EV and break-even fold rates for betting decisions.
+//! - [`range`]: opponent hand ranges such as `TT+, AKs` and equity against them.
+//! - [`short_deck`]: Short Deck Hold'em, played with the cards from six to ace.
+//! - [`stud`]: Seven Card Stud simulation with upcards and dead cards.
+//! - [`wild`]: jokers and wild values, where the best substitution can make five of a kind.
+//! - [`game`]: the [`Game`] variants the simulator can deal.
+//! - [`simulation`]: equity estimation, exact when every deal can be counted and Monte
+//!   Carlo otherwise, including Razz.
+//!
+//! ```
+//! use pokerface::{evaluate_hand, parse_cards, HandRank};
+//!
+//! let hand = parse_cards("Ah Ad");
+//! let board = parse_cards("As Kh Kd 7c 2s");
+//! assert_eq!(evaluate_hand(&hand, &board), HandRank::FullHouse(14, 13));
+//! ```
+
+pub mod card;
+pub mod card_set;
+pub mod deck;
+mod description;
+pub mod draw;
+mod enumeration;
+pub mod evaluator;
+pub mod game;
+pub mod lookup;
+pub mod low;
+pub mod omaha;
+pub mod pineapple;
+pub mod pot_odds;
+pub mod range;
+mod rng;
+pub mod short_deck;
+pub mod simulation;
+pub mod stud;
+pub mod wild;
+
+pub use card::{parse_cards, parse_value, Card};
+pub use card_set::CardSet;
+pub use deck::{
+    create_deck, create_deck_with, create_deck_without, create_short_deck, remove_known_cards,
+};
+pub use draw::{
+    analyze_discards, analyze_discards_with_budget, calculate_deuce_seven, simulate_deuce_seven,
+    DiscardOption,
+};
+pub use evaluator::{compare_hands, evaluate_best_hand, evaluate_hand, BestHand, HandRank};
+pub use game::Game;
+pub use lookup::{evaluate_set, evaluate_strength, HandStrength};
+pub use low::{evaluate_deuce_seven, evaluate_low, evaluate_low_eight, DeuceSevenRank, LowRank};
+pub use omaha::{evaluate_best_omaha_hand, evaluate_omaha, evaluate_omaha_low};
+pub use pineapple::{simulate_pineapple, simulate_pineapple_with_budget, Discard};
+pub use pot_odds::PotOdds;
+pub use range::{
+    calculate_range_equity, calculate_range_vs_range, parse_range, ComboEquity, Range, RangeEquity,
+};
+pub use short_deck::{evaluate_best_short_deck_hand, evaluate_short_deck, ShortDeckRank};
+pub use simulation::{
+    calculate_all_in, calculate_equity, calculate_hi_lo, calculate_razz, calculate_short_deck,
+    calculate_wild, simulate_game, simulate_hi_lo, simulate_poker_hand, simulate_razz,
+    simulate_short_deck, simulate_wild, Budget, Equity, HiLoResult,
+};
+pub use stud::{calculate_stud, simulate_stud};
+pub use wild::{evaluate_wild, Wilds};
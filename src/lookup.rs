@@ -0,0 +1,441 @@
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::evaluator::HandRank;
+
+/// A dense hand strength from 0 to 7461, where a larger value beats a smaller one.
+///
+/// Each of the 7462 distinct poker hands has its own value, so equal hands compare equal.
+/// The [`HandRank`] can be recovered with [`HandStrength::rank`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandStrength(u16);
+
+// Categories in ascending order, used as indices into CATEGORY_OFFSETS
+const HIGH_CARD: usize = 0;
+const ONE_PAIR: usize = 1;
+const TWO_PAIR: usize = 2;
+const THREE_OF_A_KIND: usize = 3;
+const STRAIGHT: usize = 4;
+const FLUSH: usize = 5;
+const FULL_HOUSE: usize = 6;
+const FOUR_OF_A_KIND: usize = 7;
+const STRAIGHT_FLUSH: usize = 8;
+
+// Number of distinct hands in each category
+const CATEGORY_SIZES: [u16; 9] = [1277, 2860, 858, 858, 10, 1277, 156, 156, 10];
+
+const CATEGORY_OFFSETS: [u16; 10] = {
+    let mut offsets = [0; 10];
+    let mut i = 0;
+    while i < 9 {
+        offsets[i + 1] = offsets[i] + CATEGORY_SIZES[i];
+        i += 1;
+    }
+    offsets
+};
+
+// A-2-3-4-5 as a rank mask
+const WHEEL: u16 = 0b1_0000_0000_1111;
+
+// Binomial coefficients, CHOOSE[n][k] is n choose k
+const CHOOSE: [[u16; 6]; 14] = {
+    let mut table = [[0; 6]; 14];
+    let mut n = 0;
+    while n < 14 {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k < 6 && k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+};
+
+// Highest card value (5 to 14) of the best straight in each 13 bit rank mask, 0 if none
+static STRAIGHT_HIGH: [u8; 8192] = {
+    let mut table = [0; 8192];
+    let mut mask = 0;
+    while mask < 8192 {
+        table[mask] = straight_high(mask as u16);
+        mask += 1;
+    }
+    table
+};
+
+// Position of each five rank mask among the 1277 masks that are not straights, in ascending
+// order. Masks compare like their ranks sorted from highest to lowest, so this is a ranking.
+static UNIQUE5_INDEX: [u16; 8192] = {
+    let mut table = [0; 8192];
+    let mut next = 0;
+    let mut mask = 0;
+    while mask < 8192 {
+        if (mask as u16).count_ones() == 5 && straight_high(mask as u16) == 0 {
+            table[mask] = next;
+            next += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+// The inverse of UNIQUE5_INDEX
+static UNIQUE5_MASK: [u16; 1277] = {
+    let mut table = [0; 1277];
+    let mut next = 0;
+    let mut mask = 0;
+    while mask < 8192 {
+        if (mask as u16).count_ones() == 5 && straight_high(mask as u16) == 0 {
+            table[next] = mask as u16;
+            next += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+const fn straight_high(mask: u16) -> u8 {
+    let mut high = 12;
+    while high >= 4 {
+        let window = 0b11111 << (high - 4);
+        if mask & window == window {
+            return high as u8 + 2;
+        }
+        high -= 1;
+    }
+    if mask & WHEEL == WHEEL {
+        5
+    } else {
+        0
+    }
+}
+
+/// Evaluates the best poker hand that can be made from `hand` and `board` together.
+///
+/// This gives the same ordering as [`crate::evaluate_hand`] but works on rank bitmasks and
+/// precomputed tables, without allocating.
+///
+/// # Panics
+///
+/// Panics if fewer than 5 or more than 7 cards are given.
+pub fn evaluate_strength(hand: &[Card], board: &[Card]) -> HandStrength {
+    evaluate_set(hand.iter().chain(board).collect())
+}
+
+/// Evaluates the best poker hand in a set of 5 to 7 cards.
+///
+/// # Panics
+///
+/// Panics if the set holds fewer than 5 or more than 7 cards. Jokers are not ranked here but
+/// by [`crate::evaluate_wild`], and debug builds panic on them too.
+pub fn evaluate_set(cards: CardSet) -> HandStrength {
+    assert!(
+        (5..=7).contains(&cards.len()),
+        "Invalid card count: expected 5 to 7 cards, found {}",
+        cards.len()
+    );
+    debug_assert!(
+        (cards - CardSet::full_deck()).is_empty(),
+        "Jokers can only be ranked by evaluate_wild"
+    );
+    strength_from_suits([0, 1, 2, 3].map(|suit| cards.suit_mask(suit)))
+}
+
+// Evaluates 5 to 7 distinct cards given as one 13 bit rank mask per suit
+fn strength_from_suits(suits: [u16; 4]) -> HandStrength {
+    for suited in suits {
+        if suited.count_ones() >= 5 {
+            let high = STRAIGHT_HIGH[suited as usize];
+            return if high != 0 {
+                make(STRAIGHT_FLUSH, high as u16 - 5)
+            } else {
+                make(FLUSH, UNIQUE5_INDEX[keep_highest(suited, 5) as usize])
+            };
+        }
+    }
+
+    let [s0, s1, s2, s3] = suits;
+    let ranks = s0 | s1 | s2 | s3;
+    let quads = s0 & s1 & s2 & s3;
+    let at_least_three = (s0 & s1 & s2) | (s0 & s1 & s3) | (s0 & s2 & s3) | (s1 & s2 & s3);
+    let at_least_two = (s0 & s1) | (s0 & s2) | (s0 & s3) | (s1 & s2) | (s1 & s3) | (s2 & s3);
+
+    if quads != 0 {
+        let quad = highest(quads);
+        let kicker = highest(ranks & !(1 << quad));
+        return make(FOUR_OF_A_KIND, quad * 12 + remove_bit(1 << kicker, quad).trailing_zeros() as u16);
+    }
+
+    if at_least_three != 0 {
+        let trip = highest(at_least_three);
+        let rest = at_least_two & !(1 << trip);
+        if rest != 0 {
+            let pair = highest(rest);
+            return make(FULL_HOUSE, trip * 12 + remove_bit(1 << pair, trip).trailing_zeros() as u16);
+        }
+    }
+
+    let high = STRAIGHT_HIGH[ranks as usize];
+    if high != 0 {
+        return make(STRAIGHT, high as u16 - 5);
+    }
+
+    if at_least_three != 0 {
+        let trip = highest(at_least_three);
+        let kickers = keep_highest(ranks & !(1 << trip), 2);
+        return make(THREE_OF_A_KIND, trip * 66 + colex(remove_bit(kickers, trip)));
+    }
+
+    match at_least_two.count_ones() {
+        0 => make(HIGH_CARD, UNIQUE5_INDEX[keep_highest(ranks, 5) as usize]),
+        1 => {
+            let pair = highest(at_least_two);
+            let kickers = keep_highest(ranks & !at_least_two, 3);
+            make(ONE_PAIR, pair * 220 + colex(remove_bit(kickers, pair)))
+        }
+        _ => {
+            let pairs = keep_highest(at_least_two, 2);
+            let low = pairs.trailing_zeros() as u16;
+            let high = highest(pairs);
+            let kicker = remove_bit(remove_bit(1 << highest(ranks & !pairs), high), low);
+            make(TWO_PAIR, colex(pairs) * 11 + kicker.trailing_zeros() as u16)
+        }
+    }
+}
+
+fn make(category: usize, index: u16) -> HandStrength {
+    HandStrength(CATEGORY_OFFSETS[category] + index)
+}
+
+// Index of the highest set bit
+fn highest(mask: u16) -> u16 {
+    15 - mask.leading_zeros() as u16
+}
+
+// Clears the lowest set bits until only `count` remain
+fn keep_highest(mut mask: u16, count: u32) -> u16 {
+    while mask.count_ones() > count {
+        mask &= mask - 1;
+    }
+    mask
+}
+
+// Deletes bit position `bit`, shifting the higher bits down by one
+fn remove_bit(mask: u16, bit: u16) -> u16 {
+    (mask & ((1 << bit) - 1)) | ((mask >> (bit + 1)) << bit)
+}
+
+// The inverse of remove_bit, inserting a zero at position `bit`
+fn insert_bit(mask: u16, bit: u16) -> u16 {
+    (mask & ((1 << bit) - 1)) | ((mask >> bit) << (bit + 1))
+}
+
+// Position of a mask among all masks with the same number of bits, in ascending order
+fn colex(mut mask: u16) -> u16 {
+    let mut index = 0;
+    let mut k = 1;
+    while mask != 0 {
+        index += CHOOSE[mask.trailing_zeros() as usize][k];
+        mask &= mask - 1;
+        k += 1;
+    }
+    index
+}
+
+// The inverse of colex
+fn uncolex(mut index: u16, size: usize) -> u16 {
+    let mut mask = 0;
+    for i in (1..=size).rev() {
+        let mut bit = 13;
+        while CHOOSE[bit][i] > index {
+            bit -= 1;
+        }
+        mask |= 1 << bit;
+        index -= CHOOSE[bit][i];
+    }
+    mask
+}
+
+// Card values from a rank mask, highest first
+fn values(mask: u16) -> impl Iterator<Item = u8> {
+    (0..13u8).rev().filter(move |rank| mask & (1 << rank) != 0).map(|rank| rank + 2)
+}
+
+impl HandStrength {
+    /// Returns the strength for a dense value, or `None` if `value` is 7462 or more.
+    pub fn from_value(value: u16) -> Option<HandStrength> {
+        if value < CATEGORY_OFFSETS[9] {
+            Some(HandStrength(value))
+        } else {
+            None
+        }
+    }
+
+    /// The dense value of this strength.
+    pub fn value(self) -> u16 {
+        self.0
+    }
+
+    /// Decodes the strength back into a [`HandRank`].
+    pub fn rank(self) -> HandRank {
+        let category = CATEGORY_OFFSETS.iter().rposition(|&offset| offset <= self.0).unwrap();
+        let index = self.0 - CATEGORY_OFFSETS[category];
+
+        match category {
+            HIGH_CARD | FLUSH => {
+                let mut v = values(UNIQUE5_MASK[index as usize]);
+                let mut next = || v.next().unwrap();
+                let cards = (next(), next(), next(), next(), next());
+                if category == FLUSH {
+                    HandRank::Flush(cards.0, cards.1, cards.2, cards.3, cards.4)
+                } else {
+                    HandRank::HighCard(cards.0, cards.1, cards.2, cards.3, cards.4)
+                }
+            }
+            ONE_PAIR => {
+                let pair = index / 220;
+                let mut kickers = values(insert_bit(uncolex(index % 220, 3), pair));
+                let mut next = || kickers.next().unwrap();
+                HandRank::OnePair(pair as u8 + 2, next(), next(), next())
+            }
+            TWO_PAIR => {
+                let pairs = uncolex(index / 11, 2);
+                let low = pairs.trailing_zeros() as u16;
+                let high = highest(pairs);
+                let kicker = insert_bit(insert_bit(1 << (index % 11), low), high);
+                HandRank::TwoPair(high as u8 + 2, low as u8 + 2, highest(kicker) as u8 + 2)
+            }
+            THREE_OF_A_KIND => {
+                let trip = index / 66;
+                let mut kickers = values(insert_bit(uncolex(index % 66, 2), trip));
+                let mut next = || kickers.next().unwrap();
+                HandRank::ThreeOfAKind(trip as u8 + 2, next(), next())
+            }
+            STRAIGHT => HandRank::Straight(index as u8 + 5),
+            FULL_HOUSE | FOUR_OF_A_KIND => {
+                let main = index / 12;
+                let other = highest(insert_bit(1 << (index % 12), main)) as u8 + 2;
+                if category == FULL_HOUSE {
+                    HandRank::FullHouse(main as u8 + 2, other)
+                } else {
+                    HandRank::FourOfAKind(main as u8 + 2, other)
+                }
+            }
+            _ => match index as u8 + 5 {
+                14 => HandRank::RoyalFlush,
+                high => HandRank::StraightFlush(high),
+            },
+        }
+    }
+}
+
+impl From<HandStrength> for HandRank {
+    fn from(strength: HandStrength) -> HandRank {
+        strength.rank()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use crate::deck::create_deck;
+    use crate::evaluator::evaluate_hand;
+
+    #[test]
+    fn test_category_offsets() {
+        assert_eq!(CATEGORY_OFFSETS[9], 7462);
+        assert_eq!(UNIQUE5_MASK[1276], 0b1_1110_1000_0000);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Jokers can only be ranked by evaluate_wild")]
+    fn test_jokers_are_not_ranked() {
+        evaluate_strength(&parse_cards("X1 Ah"), &parse_cards("As Ad Kc 7h 2d"));
+    }
+
+    #[test]
+    fn test_strengths_decode_in_order() {
+        for value in 0..7461 {
+            let lower = HandStrength(value).rank();
+            let higher = HandStrength(value + 1).rank();
+            assert!(lower < higher, "{:?} {:?}", lower, higher);
+        }
+        assert_eq!(HandStrength(0).rank(), HandRank::HighCard(7, 5, 4, 3, 2));
+        assert_eq!(HandStrength(7461).rank(), HandRank::RoyalFlush);
+    }
+
+    #[test]
+    fn test_from_value() {
+        assert_eq!(HandStrength::from_value(7461).map(HandStrength::value), Some(7461));
+        assert_eq!(HandStrength::from_value(7462), None);
+    }
+
+    #[test]
+    fn test_matches_evaluate_hand() {
+        use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut deck = create_deck();
+        for card_count in 5..=7 {
+            for _ in 0..5000 {
+                deck.shuffle(&mut rng);
+                let (hand, board) = deck[..card_count].split_at(2);
+                assert_eq!(
+                    evaluate_strength(hand, board).rank(),
+                    evaluate_hand(hand, board),
+                    "{:?} {:?}",
+                    hand,
+                    board
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_strength() {
+        let cases = [
+            ("Ah Kd", "Qc Js 9h 3d 2c", HandRank::HighCard(14, 13, 12, 11, 9)),
+            ("7h 7d", "Ac Ks 9h 3d 2c", HandRank::OnePair(7, 14, 13, 9)),
+            ("7h 7d", "Ac As 9h 9d 2c", HandRank::TwoPair(14, 9, 7)),
+            ("7h 7d", "7c Ks 9h 3d 2c", HandRank::ThreeOfAKind(7, 13, 9)),
+            ("5h 5d", "5c 4s 3h 2d Ac", HandRank::Straight(5)),
+            ("Ah Jh", "9h 7h 3h 2h Kd", HandRank::Flush(14, 11, 9, 7, 3)),
+            ("7h 7d", "7c 9s 9h 9d 2c", HandRank::FullHouse(9, 7)),
+            ("7h 7d", "7c 7s 9h 9d 9c", HandRank::FourOfAKind(7, 9)),
+            ("5h 4h", "3h 2h Ah 6h 9d", HandRank::StraightFlush(6)),
+            ("Ah Kh", "Qh Jh Th 9h 8h", HandRank::RoyalFlush),
+        ];
+        for (hand, board, expected) in cases {
+            let strength = evaluate_strength(&parse_cards(hand), &parse_cards(board));
+            assert_eq!(HandRank::from(strength), expected, "{} {}", hand, board);
+            let cards: CardSet = parse_cards(&format!("{} {}", hand, board)).iter().collect();
+            assert_eq!(evaluate_set(cards), strength);
+        }
+    }
+
+    // Walks all 2,598,960 five card hands, which is only quick in release builds
+    #[test]
+    #[cfg_attr(debug_assertions, ignore)]
+    fn test_every_five_card_hand_matches_evaluate_hand() {
+        let deck = create_deck();
+        let mut seen = vec![false; 7462];
+        for a in 0..52 {
+            for b in a + 1..52 {
+                for c in b + 1..52 {
+                    for d in c + 1..52 {
+                        for e in d + 1..52 {
+                            let hand = [deck[a], deck[b]];
+                            let board = [deck[c], deck[d], deck[e]];
+                            let strength = evaluate_strength(&hand, &board);
+                            assert_eq!(strength.rank(), evaluate_hand(&hand, &board));
+                            seen[strength.value() as usize] = true;
+                        }
+                    }
+                }
+            }
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
@@ -0,0 +1,367 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::card::Card;
+use crate::evaluator::HandRank;
+use crate::lookup::evaluate_set;
+
+/// An ace-to-five low hand, where aces play low and straights and flushes do not count.
+///
+/// Unpaired hands are the best lows, followed by one pair, two pair, three of a kind, a
+/// full house and four of a kind. A better low compares greater, so the winning low is the
+/// maximum, just like with [`crate::HandRank`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LowRank(u32);
+
+// Ranks from ace to eight, the cards that can make an eight-or-better low
+const EIGHT_OR_BETTER: u16 = 0xFF;
+
+impl LowRank {
+    /// The five card values with aces as 1, the ones that decide the hand first: paired
+    /// cards before unpaired ones, then from highest to lowest.
+    pub fn values(self) -> [u8; 5] {
+        let mut values = [0; 5];
+        for (i, value) in values.iter_mut().enumerate() {
+            *value = (self.0 >> (16 - 4 * i) & 0xF) as u8;
+        }
+        values
+    }
+
+    /// Whether the hand has no paired cards.
+    pub fn is_unpaired(self) -> bool {
+        self.0 >> 20 == 0
+    }
+}
+
+// The key holds a category for how the hand is paired, followed by the five values in
+// values() order, so a smaller key is a better low
+impl Ord for LowRank {
+    fn cmp(&self, other: &LowRank) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for LowRank {
+    fn partial_cmp(&self, other: &LowRank) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats the low in [`LowRank::values`] order, such as `8-6-4-2-A` or `6-6-4-2-A`.
+impl fmt::Display for LowRank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", dashed(&self.values()))
+    }
+}
+
+/// A deuce-to-seven low hand, where aces are always high and straights and flushes count
+/// against the hand, so the worst high hand is the best low.
+///
+/// A better low compares greater, so the winning low is the maximum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DeuceSevenRank(HandRank);
+
+impl DeuceSevenRank {
+    /// The hand as a high hand, with `A-5-4-3-2` as an ace high and not a straight.
+    pub fn high_rank(self) -> HandRank {
+        self.0
+    }
+}
+
+impl Ord for DeuceSevenRank {
+    fn cmp(&self, other: &DeuceSevenRank) -> Ordering {
+        other.0.cmp(&self.0)
+    }
+}
+
+impl PartialOrd for DeuceSevenRank {
+    fn partial_cmp(&self, other: &DeuceSevenRank) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Formats a plain low such as `7-5-4-3-2`, and any other hand like its [`HandRank`].
+impl fmt::Display for DeuceSevenRank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            HandRank::HighCard(a, b, c, d, e) => write!(f, "{}", dashed(&[a, b, c, d, e])),
+            rank => write!(f, "{}", rank),
+        }
+    }
+}
+
+// Card values joined by dashes, such as "8-6-4-2-A"
+fn dashed(values: &[u8]) -> String {
+    let names: Vec<String> = values
+        .iter()
+        .map(|&value| match value {
+            1 | 14 => "A".to_string(),
+            10 => "T".to_string(),
+            11 => "J".to_string(),
+            12 => "Q".to_string(),
+            13 => "K".to_string(),
+            _ => value.to_string(),
+        })
+        .collect();
+    names.join("-")
+}
+
+// The value of a card when aces play low
+fn low_value(card: Card) -> u8 {
+    if card.value == 14 {
+        1
+    } else {
+        card.value
+    }
+}
+
+// The low mask bit of a card, with aces as the lowest bit
+pub(crate) fn low_bit(card: Card) -> u16 {
+    1 << (low_value(card) - 1)
+}
+
+// Low mask of the cards that can play in an eight-or-better low
+pub(crate) fn eight_or_better_mask(cards: &[Card]) -> u16 {
+    cards.iter().fold(0, |mask, &card| mask | low_bit(card)) & EIGHT_OR_BETTER
+}
+
+// An unpaired low from a mask of exactly five low values
+pub(crate) fn low_from_mask(mut mask: u16) -> LowRank {
+    let mut key = 0;
+    for i in 0..5 {
+        key |= (mask.trailing_zeros() + 1) << (4 * i);
+        mask &= mask - 1;
+    }
+    LowRank(key)
+}
+
+// The low of exactly five cards
+fn five_card_low(cards: [Card; 5]) -> LowRank {
+    let mut values = cards.map(low_value);
+    let mut counts = [0u8; 14];
+    for &value in &values {
+        counts[value as usize] += 1;
+    }
+    values.sort_by_key(|&value| std::cmp::Reverse((counts[value as usize], value)));
+
+    let distinct = counts.iter().filter(|&&count| count > 0).count();
+    let category = match (counts[values[0] as usize], distinct) {
+        (1, _) => 0,
+        (2, 4) => 1,
+        (2, _) => 2,
+        (3, 3) => 3,
+        (3, _) => 4,
+        _ => 5,
+    };
+    let key = values
+        .iter()
+        .fold(category, |key, &value| key << 4 | value as u32);
+    LowRank(key)
+}
+
+/// Evaluates the best ace-to-five low that can be made from any five of `cards`, as
+/// played in Razz.
+///
+/// Pairs are avoided whenever there are five different ranks, and otherwise count against
+/// the hand.
+///
+/// # Panics
+///
+/// Panics if fewer than 5 or more than 7 cards are given.
+pub fn evaluate_low(cards: &[Card]) -> LowRank {
+    assert!(
+        (5..=7).contains(&cards.len()),
+        "Invalid card count: expected 5 to 7 cards, found {}",
+        cards.len()
+    );
+
+    let mut mask = cards.iter().fold(0, |mask, &card| mask | low_bit(card));
+    if mask.count_ones() >= 5 {
+        while mask.count_ones() > 5 {
+            mask &= !(1 << (15 - mask.leading_zeros()));
+        }
+        return low_from_mask(mask);
+    }
+
+    // Paired hands are rare, so try every five card subset
+    let mut best = None;
+    for subset in 0u32..(1 << cards.len()) {
+        if subset.count_ones() == 5 {
+            let mut hand = [cards[0]; 5];
+            for (slot, index) in (0..cards.len())
+                .filter(|i| subset & (1 << i) != 0)
+                .enumerate()
+            {
+                hand[slot] = cards[index];
+            }
+            best = best.max(Some(five_card_low(hand)));
+        }
+    }
+    best.unwrap()
+}
+
+/// Evaluates a five card deuce-to-seven low, as played in single and triple draw.
+///
+/// # Panics
+///
+/// Panics if `cards` does not hold exactly 5 cards.
+pub fn evaluate_deuce_seven(cards: &[Card]) -> DeuceSevenRank {
+    assert_eq!(
+        cards.len(),
+        5,
+        "Invalid hand length: expected 5 cards, found {}",
+        cards.len()
+    );
+    let rank = match evaluate_set(cards.iter().collect()).rank() {
+        // The ace only plays high, so the wheel is no straight
+        HandRank::Straight(5) => HandRank::HighCard(14, 5, 4, 3, 2),
+        HandRank::StraightFlush(5) => HandRank::Flush(14, 5, 4, 3, 2),
+        rank => rank,
+    };
+    DeuceSevenRank(rank)
+}
+
+/// Evaluates the best eight-or-better low that can be made from any five of `cards`.
+///
+/// Returns `None` when there are not five different ranks from ace to eight.
+pub fn evaluate_low_eight(cards: &[Card]) -> Option<LowRank> {
+    best_low_eight(eight_or_better_mask(cards))
+}
+
+// The five lowest ranks of an eight-or-better mask, if there are five
+pub(crate) fn best_low_eight(mut mask: u16) -> Option<LowRank> {
+    if mask.count_ones() < 5 {
+        return None;
+    }
+    let mut low = 0;
+    for _ in 0..5 {
+        low |= mask & mask.wrapping_neg();
+        mask &= mask - 1;
+    }
+    Some(low_from_mask(low))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn low(cards: &str) -> Option<String> {
+        evaluate_low_eight(&parse_cards(cards)).map(|low| low.to_string())
+    }
+
+    #[test]
+    fn test_evaluate_low_eight() {
+        assert_eq!(low("Ah 2d 3c 4s 5h"), Some("5-4-3-2-A".to_string()));
+        assert_eq!(low("8h 6d 4c 2s Ah Kd Kc"), Some("8-6-4-2-A".to_string()));
+        assert_eq!(low("8h 7d 6c 5s 4h 3d 2c"), Some("6-5-4-3-2".to_string()));
+    }
+
+    #[test]
+    fn test_pairs_do_not_count() {
+        assert_eq!(low("Ah Ad 3c 4s 5h 5d"), None);
+        assert_eq!(low("Ah Ad 3c 4s 5h 6d"), Some("6-5-4-3-A".to_string()));
+    }
+
+    #[test]
+    fn test_nine_does_not_qualify() {
+        assert_eq!(low("9h 6d 4c 2s Ah"), None);
+    }
+
+    #[test]
+    fn test_low_ordering() {
+        let wheel = evaluate_low_eight(&parse_cards("Ah 2d 3c 4s 5h")).unwrap();
+        let six = evaluate_low_eight(&parse_cards("Ah 2d 3c 4s 6h")).unwrap();
+        let eight_six = evaluate_low_eight(&parse_cards("Ah 2d 3c 6s 8h")).unwrap();
+        let eight_seven = evaluate_low_eight(&parse_cards("Ah 2d 3c 7s 8h")).unwrap();
+        assert!(wheel > six);
+        assert!(six > eight_six);
+        assert!(eight_six > eight_seven);
+        assert_eq!(eight_seven.values(), [8, 7, 3, 2, 1]);
+    }
+
+    fn razz(cards: &str) -> String {
+        evaluate_low(&parse_cards(cards)).to_string()
+    }
+
+    #[test]
+    fn test_evaluate_low_ignores_straights_and_flushes() {
+        assert_eq!(razz("Ah 2h 3h 4h 5h"), "5-4-3-2-A");
+        assert_eq!(razz("Kh Qd 9c 8s 7h 6d 5c"), "9-8-7-6-5");
+    }
+
+    #[test]
+    fn test_evaluate_low_avoids_pairs() {
+        assert_eq!(razz("Ah Ad 2c 2s 3h 4d 6c"), "6-4-3-2-A");
+        assert!(evaluate_low(&parse_cards("Ah Ad 2c 2s 3h 4d 6c")).is_unpaired());
+    }
+
+    #[test]
+    fn test_evaluate_low_paired_hands() {
+        assert_eq!(razz("Ah Ad 2c 2s 3h 4d 4c"), "A-A-4-3-2");
+        // One pair with a king beats any two pair
+        assert_eq!(razz("Ah Ad 2c 2s 3h 3d Kc"), "A-A-K-3-2");
+        assert_eq!(razz("Ah Ad Ac 2s 2h 3d 3c"), "2-2-A-A-3");
+        assert_eq!(razz("Kh Kd Kc Ks Qh Qd Qc"), "Q-Q-Q-K-K");
+    }
+
+    #[test]
+    fn test_low_category_order() {
+        let lows: Vec<LowRank> = [
+            "Kh Qd Jc Ts 9h",
+            "Ah Ad 2c 3s 4h",
+            "Kh Kd Qc Qs Jh",
+            "Ah Ad Ac 2s 3h",
+            "Ah Ad Ac 2s 2h",
+            "Ah Ad Ac As 2h",
+        ]
+        .iter()
+        .map(|cards| evaluate_low(&parse_cards(cards)))
+        .collect();
+        for pair in lows.windows(2) {
+            assert!(pair[0] > pair[1], "{} {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_eight_or_better_matches_razz_low() {
+        let cards = parse_cards("8h 6d 4c 2s Ah Kd Kc");
+        assert_eq!(evaluate_low_eight(&cards), Some(evaluate_low(&cards)));
+    }
+
+    fn deuce_seven(cards: &str) -> DeuceSevenRank {
+        evaluate_deuce_seven(&parse_cards(cards))
+    }
+
+    #[test]
+    fn test_deuce_seven_order() {
+        let lows = [
+            "7h 5d 4c 3s 2h",
+            "7h 6d 4c 3s 2h",
+            "8h 5d 4c 3s 2h",
+            "Kh Qd Jc 9s 8h",
+            "Ah 5d 4c 3s 2h",
+            "2h 2d 3c 4s 5h",
+            "7h 6d 5c 4s 3h",
+            "7h 5h 4h 3h 2h",
+        ]
+        .map(deuce_seven);
+        for pair in lows.windows(2) {
+            assert!(pair[0] > pair[1], "{} {}", pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn test_deuce_seven_wheel_is_ace_high() {
+        assert_eq!(
+            deuce_seven("Ah 5d 4c 3s 2h").high_rank(),
+            HandRank::HighCard(14, 5, 4, 3, 2)
+        );
+        assert_eq!(deuce_seven("Ah 5d 4c 3s 2h").to_string(), "A-5-4-3-2");
+        assert_eq!(deuce_seven("7h 5d 4c 3s 2h").to_string(), "7-5-4-3-2");
+        assert_eq!(
+            deuce_seven("6h 5d 4c 3s 2h").to_string(),
+            "Straight, Six high"
+        );
+    }
+}
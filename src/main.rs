@@ -1,979 +1,732 @@
-use clap::{Arg, Command};
-use rand::seq::SliceRandom;
-use rayon::prelude::*;
+use std::time::Duration;
 
-use std::{cmp::max, collections::HashMap};
+use clap::{Arg, Command};
+use pokerface::{
+    analyze_discards_with_budget, calculate_all_in, calculate_deuce_seven, calculate_equity,
+    calculate_hi_lo, calculate_range_equity, calculate_range_vs_range, calculate_razz,
+    calculate_short_deck, calculate_stud, calculate_wild, evaluate_best_short_deck_hand,
+    evaluate_deuce_seven, evaluate_low, evaluate_wild, parse_cards, parse_range, parse_value,
+    simulate_pineapple_with_budget, BestHand, Budget, Card, Discard, DiscardOption, Equity, Game,
+    PotOdds, Range, Wilds,
+};
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct Card {
-    // value from 2 to 14, representing 2 to 10 and Jack, Queen, King, Ace
-    value: u8,
-    // suit from 0 to 3, representing 4 suits
-    suit: u8,
-}
+fn main() {
+    let matches = Command::new("Poker Hand Simulator")
+        .version("1.0")
+        .author("Your Name")
+        .about("Simulates a poker hand")
+        .arg(
+            Arg::new("hand")
+                .short('h')
+                .long("hand")
+                .value_name("HAND")
+                .help("Sets the hand to evaluate")
+                .takes_value(true)
+                .required_unless_present("range")
+                .conflicts_with("range"),
+        )
+        .arg(
+            Arg::new("range")
+                .short('r')
+                .long("range")
+                .value_name("RANGE")
+                .help("Sets a Hold'em hero range to evaluate against the villain ranges, instead of one hand")
+                .takes_value(true)
+                .requires("villain"),
+        )
+        .arg(
+            Arg::new("board")
+                .short('b')
+                .long("board")
+                .value_name("BOARD")
+                .help("Sets the board cards")
+                .takes_value(true)
+                .default_value(""),
+        )
+        .arg(
+            Arg::new("game")
+                .short('g')
+                .long("game")
+                .value_name("GAME")
+                .help("Sets the game; omaha takes 4, 5 or 6 hole cards, the pineapple games 3, draw 5 cards, stud and razz up to 7 known cards and the 2-7 draw games the cards to keep")
+                .takes_value(true)
+                .possible_values([
+                    "holdem",
+                    "omaha",
+                    "short-deck",
+                    "pineapple",
+                    "crazy-pineapple",
+                    "draw",
+                    "stud",
+                    "razz",
+                    "single-draw",
+                    "triple-draw",
+                ])
+                .default_value("holdem"),
+        )
+        .arg(
+            Arg::new("hi-lo")
+                .long("hi-lo")
+                .help("Splits the pot with the best eight-or-better low in Hold'em or Omaha"),
+        )
+        .arg(
+            Arg::new("upcards")
+                .short('u')
+                .long("upcards")
+                .value_name("UPCARDS")
+                .help("Sets one stud opponent's upcards; repeat for each opponent")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .arg(
+            Arg::new("dead")
+                .long("dead")
+                .value_name("DEAD")
+                .help("Sets cards that are out of play, such as mucked or folded cards")
+                .takes_value(true)
+                .default_value(""),
+        )
+        .arg(
+            Arg::new("villain")
+                .short('v')
+                .long("villain")
+                .value_name("RANGE")
+                .help("Sets one Hold'em opponent's range, such as \"TT+, AKs, A5s-A2s:0.5\" with optional frequencies; repeat for each opponent")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .arg(
+            Arg::new("jokers")
+                .long("jokers")
+                .value_name("JOKERS")
+                .help("Adds up to 4 wild jokers, X1 to X4, to the Hold'em deck")
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::new("wild")
+                .long("wild")
+                .value_name("VALUES")
+                .help("Declares card values wild in Hold'em, such as 2 for deuces wild")
+                .takes_value(true)
+                .default_value(""),
+        )
+        .arg(
+            Arg::new("iterations")
+                .long("iterations")
+                .value_name("COUNT")
+                .help("Sets the most deals to sample, or to count for exact rates")
+                .takes_value(true)
+                .default_value("1000000"),
+        )
+        .arg(
+            Arg::new("target-error")
+                .long("target-error")
+                .value_name("ERROR")
+                .help("Stops sampling once every rate has this standard error, such as 0.001")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("time-limit")
+                .long("time-limit")
+                .value_name("SECONDS")
+                .help("Stops sampling after this many seconds for each result")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("seed")
+                .long("seed")
+                .value_name("SEED")
+                .help("Seeds the random deals so every run gives the same results")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("pot")
+                .long("pot")
+                .value_name("CHIPS")
+                .help("Gives the pot, including any bet to call, for pot odds and bet EV")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("to-call")
+                .long("to-call")
+                .value_name("CHIPS")
+                .help("Sets the bet the hero faces in the pot")
+                .takes_value(true)
+                .default_value("0"),
+        )
+        .arg(
+            Arg::new("stack")
+                .long("stack")
+                .value_name("CHIPS")
+                .help("Sets the effective stack, capping calls and giving the all-in shove")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::new("bet")
+                .long("bet")
+                .value_name("CHIPS")
+                .help("Gives the fold rate a bet or raise of this many chips needs")
+                .takes_value(true),
+        )
+        .get_matches();
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
-enum HandRank {
-    // HighCard has 5 different values, sorted from highest to lowest
-    HighCard(u8, u8, u8, u8, u8),
-    // OnePair has 1 pair and 3 kickers, sorted from highest to lowest
-    OnePair(u8, u8, u8, u8),
-    // TwoPair has 2 pairs and 1 kicker, sorted from highest to lowest
-    TwoPair(u8, u8, u8),
-    // ThreeOfAKind has 1 triple and 2 kickers, sorted from highest to lowest
-    ThreeOfAKind(u8, u8, u8),
-    // Straight has 1 high card
-    Straight(u8),
-    // Flush has 5 different values with the same suit, sorted from highest to lowest
-    Flush(u8, u8, u8, u8, u8),
-    // FullHouse has 1 triple and 1 pair
-    FullHouse(u8, u8),
-    // FourOfAKind has 1 quadruple and 1 kicker
-    FourOfAKind(u8, u8),
-    // StraightFlush has 1 high card, all cards with the same suit
-    StraightFlush(u8),
-    // RoyalFlush is a special case of StraightFlush, with the highest card being Ace
-    RoyalFlush,
-}
+    let hand_input = matches.value_of("hand").unwrap_or("");
+    let board_input = matches.value_of("board").unwrap();
 
-fn evaluate_hand(hand: &[Card], board: &[Card]) -> HandRank {
-    let mut all_cards = hand.to_vec();
-    all_cards.extend_from_slice(board);
-    all_cards.sort_by(|a, b| b.value.cmp(&a.value));
-
-    if let Some(flush_cards) = check_flush(&all_cards) {
-        if let Some(straight_values) = check_straight(&flush_cards) {
-            return if straight_values.contains(&14) {
-                HandRank::RoyalFlush
-            } else {
-                HandRank::StraightFlush(*straight_values.iter().max().unwrap())
-            };
+    let hand_vec = parse_cards(hand_input);
+    let board_vec = parse_cards(board_input);
+    let dead = parse_cards(matches.value_of("dead").unwrap());
+    // "??" stands for a hole card that is dealt at random
+    let hand_known = hand_vec.iter().all(|card| !card.is_unknown());
+
+    let jokers: u8 = matches
+        .value_of("jokers")
+        .unwrap()
+        .parse()
+        .expect("Invalid joker count");
+    let wild_values = matches.value_of("wild").unwrap();
+    if (jokers > 0 || !wild_values.is_empty()) && matches.value_of("game") != Some("holdem") {
+        panic!("Wild cards are only supported in Hold'em");
+    }
+    if matches.is_present("hi-lo") && !matches!(matches.value_of("game"), Some("holdem" | "omaha"))
+    {
+        panic!("Hi-Lo is only supported in Hold'em and Omaha");
+    }
+    if matches.is_present("hi-lo") && (jokers > 0 || !wild_values.is_empty()) {
+        panic!("Hi-Lo is not supported with wild cards");
+    }
+    if matches.is_present("upcards") && matches.value_of("game") != Some("stud") {
+        panic!("Upcards are only supported in Stud");
+    }
+    // Ranges are only played in plain Hold'em, and every other game returns before they are read
+    let ranges_supported = matches.value_of("game") == Some("holdem")
+        && !matches.is_present("hi-lo")
+        && jokers == 0
+        && wild_values.is_empty();
+    if matches.is_present("range") && !ranges_supported {
+        panic!("Hero ranges are only supported in Hold'em");
+    }
+    if matches.is_present("villain") && !ranges_supported {
+        panic!("Villain ranges are only supported in Hold'em");
+    }
+    // Jokers are only in the deck once --jokers adds them, and only wild card games rank them
+    let upcards: Vec<Card> = matches
+        .values_of("upcards")
+        .into_iter()
+        .flatten()
+        .flat_map(parse_cards)
+        .collect();
+    let cards = hand_vec
+        .iter()
+        .chain(&board_vec)
+        .chain(&dead)
+        .chain(&upcards);
+    if let Some(joker) = cards
+        .filter(|card| card.is_joker())
+        .find(|card| card.suit >= jokers)
+    {
+        panic!("Joker {} is not in the deck: add it with --jokers", joker);
+    }
+
+    let mut budget = Budget::iterations(
+        matches
+            .value_of("iterations")
+            .unwrap()
+            .parse()
+            .expect("Invalid iteration count"),
+    );
+    if let Some(error) = matches.value_of("target-error") {
+        budget = budget.with_target_error(error.parse().expect("Invalid target error"));
+    }
+    if let Some(seconds) = matches.value_of("time-limit") {
+        let seconds: f64 = seconds.parse().expect("Invalid time limit");
+        budget = budget.with_time_limit(Duration::from_secs_f64(seconds));
+    }
+    if let Some(seed) = matches.value_of("seed") {
+        budget = budget.with_seed(seed.parse().expect("Invalid seed"));
+    }
+
+    let decision = matches.value_of("pot").map(|pot| Decision {
+        odds: PotOdds::new(
+            pot.parse().expect("Invalid pot"),
+            matches
+                .value_of("to-call")
+                .unwrap()
+                .parse()
+                .expect("Invalid bet to call"),
+            matches
+                .value_of("stack")
+                .map_or(f64::INFINITY, |stack| stack.parse().expect("Invalid stack")),
+        ),
+        bet: matches
+            .value_of("bet")
+            .map(|bet| bet.parse().expect("Invalid bet")),
+    });
+    if decision.is_none() && (matches.is_present("bet") || matches.is_present("stack")) {
+        panic!("Bets and stacks need the --pot they go into");
+    }
+    if decision.is_some()
+        && (!matches!(matches.value_of("game"), Some("holdem" | "omaha"))
+            || matches.is_present("hi-lo"))
+    {
+        panic!("Pot odds are only supported in Hold'em and Omaha");
+    }
+
+    if matches.value_of("game") == Some("razz") {
+        if hand_vec.len() > 7 {
+            panic!(
+                "Invalid hand length: expected at most 7 cards, found {}",
+                hand_vec.len()
+            );
+        }
+        if !board_vec.is_empty() {
+            panic!("Razz has no board cards");
+        }
+        reject_unknown(&hand_vec, "Razz");
+        if hand_vec.len() == 7 {
+            println!("Best low: {}", evaluate_low(&hand_vec));
+        }
+        for num_players in 2..=5 {
+            print_equity(
+                num_players,
+                calculate_razz(&hand_vec, &dead, num_players, budget),
+                None,
+            );
+        }
+        return;
+    }
+
+    if let Some(draws) = match matches.value_of("game") {
+        Some("single-draw") => Some(1),
+        Some("triple-draw") => Some(3),
+        _ => None,
+    } {
+        if hand_vec.len() > 5 {
+            panic!(
+                "Invalid hand length: expected at most 5 cards, found {}",
+                hand_vec.len()
+            );
+        }
+        if !board_vec.is_empty() {
+            panic!("Draw games have no board cards");
+        }
+        reject_unknown(&hand_vec, "deuce-to-seven draw");
+        if hand_vec.len() == 5 {
+            println!("Pat hand: {}", evaluate_deuce_seven(&hand_vec));
+        }
+        for num_players in 2..=5 {
+            print_equity(
+                num_players,
+                calculate_deuce_seven(&hand_vec, &dead, draws, num_players, budget),
+                None,
+            );
+        }
+        return;
+    }
+
+    if let Some(discard) = match matches.value_of("game") {
+        Some("pineapple") => Some(Discard::Preflop),
+        Some("crazy-pineapple") => Some(Discard::AfterFlop),
+        _ => None,
+    } {
+        if hand_vec.len() != 3 {
+            panic!(
+                "Invalid hand length: expected 3 cards, found {}",
+                hand_vec.len()
+            );
+        }
+        reject_unknown(&hand_vec, "Pineapple");
+        reject_unknown(&board_vec, "Pineapple");
+        for num_players in 2..=5 {
+            let options = simulate_pineapple_with_budget(
+                &hand_vec,
+                &board_vec,
+                &dead,
+                num_players,
+                discard,
+                budget,
+            );
+            println!(
+                "Number of players: {}. Recommended hold: {}",
+                num_players,
+                format_hold(&options[0].hold)
+            );
+            print_discard_options(&options);
         }
-        return HandRank::Flush(flush_cards[0].value, flush_cards[1].value, flush_cards[2].value, flush_cards[3].value, flush_cards[4].value);
+        return;
     }
 
-    let (fours, threes, pairs, singles) = check_multiples(&all_cards);
-
-    if let Some(four_value) = fours {
-        let kicker = std::cmp::max(
-            threes.first().cloned().unwrap_or(0),
-            std::cmp::max(
-                pairs.first().cloned().unwrap_or(0),
-                singles.first().cloned().unwrap_or(0)
-            )
-        );
-        return HandRank::FourOfAKind(four_value, kicker);
-    } else if threes.len() == 2 || (threes.len() == 1 && !pairs.is_empty()) {
-        let (triple, pair) = if threes.len() == 2 {
-            (threes[0], threes[1])
-        } else {
-            (threes[0], pairs[0])
+    if matches.value_of("game") == Some("draw") {
+        if hand_vec.len() != 5 {
+            panic!(
+                "Invalid hand length: expected 5 cards, found {}",
+                hand_vec.len()
+            );
+        }
+        if !board_vec.is_empty() {
+            panic!("Draw games have no board cards");
+        }
+        reject_unknown(&hand_vec, "Five Card Draw");
+        // Each hold is played out 100000 times unless --iterations says otherwise
+        let budget = match matches.occurrences_of("iterations") {
+            0 => Budget {
+                iterations: 100000,
+                ..budget
+            },
+            _ => budget,
         };
-        return HandRank::FullHouse(triple, pair);
-    } else if threes.len() == 1 && singles.len() == 4 {
-        return HandRank::ThreeOfAKind(threes[0], singles[0], singles[1]);
-    }
-
-    if let Some(straight_values) = check_straight(&all_cards) {
-        return HandRank::Straight(*straight_values.iter().max().unwrap());
+        for num_players in 2..=5 {
+            let options = analyze_discards_with_budget(&hand_vec, &dead, num_players, budget);
+            println!(
+                "Number of players: {}. Recommended hold: {}",
+                num_players,
+                format_hold(&options[0].hold)
+            );
+            print_discard_options(&options);
+        }
+        return;
     }
 
-    match (pairs.len(), singles.len()) {
-        (3, _) => HandRank::TwoPair(pairs[0], pairs[1], max(pairs[2], singles[0])),
-        (2, _) => HandRank::TwoPair(pairs[0], pairs[1], *singles.iter().max().unwrap()),
-        (1, 5) => HandRank::OnePair(pairs[0], singles[0], singles[1], singles[2]),
-        (0, 7) => HandRank::HighCard(singles[0], singles[1], singles[2], singles[3], singles[4]),
-        _ => unreachable!(),
+    if matches.value_of("game") == Some("stud") {
+        if !board_vec.is_empty() {
+            panic!("Stud has no board cards");
+        }
+        reject_unknown(&hand_vec, "Stud");
+        reject_unknown(&dead, "Stud");
+        if let Some(upcards) = matches.values_of("upcards") {
+            let opponents: Vec<Vec<Card>> = upcards.map(parse_cards).collect();
+            reject_unknown(&opponents.concat(), "Stud");
+            print_equity(
+                opponents.len() + 1,
+                calculate_stud(&hand_vec, &opponents, &dead, budget),
+                None,
+            );
+        } else {
+            for num_players in 2..=5 {
+                let opponents = vec![Vec::new(); num_players - 1];
+                print_equity(
+                    num_players,
+                    calculate_stud(&hand_vec, &opponents, &dead, budget),
+                    None,
+                );
+            }
+        }
+        return;
     }
-}
 
-fn compare_hands(hand1: HandRank, hand2: HandRank) -> i32 {
-    use HandRank::*;
-
-    match (hand1, hand2) {
-        (RoyalFlush, RoyalFlush) => 0,
-        (StraightFlush(high_card1), StraightFlush(high_card2)) => {
-            high_card1.cmp(&high_card2) as i32
+    if matches.value_of("game") == Some("short-deck") {
+        if hand_vec.len() != 2 {
+            panic!(
+                "Invalid hand length: expected 2 cards, found {}",
+                hand_vec.len()
+            );
         }
-        (FourOfAKind(four1, kicker1), FourOfAKind(four2, kicker2)) => {
-            let cards1 = vec![four1, kicker1];
-            let cards2 = vec![four2, kicker2];
-            compare_cards(&cards1, &cards2)
+        if board_vec.len() == 5 && hand_known {
+            print_best_hand(evaluate_best_short_deck_hand(&hand_vec, &board_vec));
         }
-        (FullHouse(three1, pair1), FullHouse(three2, pair2)) => {
-            let cards1 = vec![three1, pair1];
-            let cards2 = vec![three2, pair2];
-            compare_cards(&cards1, &cards2)
+        for num_players in 2..=5 {
+            print_equity(
+                num_players,
+                calculate_short_deck(&hand_vec, &board_vec, &dead, num_players, budget),
+                None,
+            );
         }
-        (Flush(a1, b1, c1, d1, e1), Flush(a2, b2, c2, d2, e2)) => {
-            let cards1 = vec![a1, b1, c1, d1, e1];
-            let cards2 = vec![a2, b2, c2, d2, e2];
-            compare_cards(&cards1, &cards2)
+        return;
+    }
+
+    if jokers > 0 || !wild_values.is_empty() {
+        if decision.is_some() {
+            panic!("Pot odds are not supported with wild cards");
         }
-        (Straight(high_card1), Straight(high_card2)) => high_card1.cmp(&high_card2) as i32,
-        (
-            ThreeOfAKind(three1, kicker1_1, kicker1_2),
-            ThreeOfAKind(three2, kicker2_1, kicker2_2),
-        ) => {
-            let cards1 = vec![three1, kicker1_1, kicker1_2];
-            let cards2 = vec![three2, kicker2_1, kicker2_2];
-            compare_cards(&cards1, &cards2)
+        if hand_vec.len() != 2 {
+            panic!(
+                "Invalid hand length: expected 2 cards, found {}",
+                hand_vec.len()
+            );
         }
-        (TwoPair(high_pair1, low_pair1, kicker1), TwoPair(high_pair2, low_pair2, kicker2)) => {
-            let cards1 = vec![high_pair1, low_pair1, kicker1];
-            let cards2 = vec![high_pair2, low_pair2, kicker2];
-            compare_cards(&cards1, &cards2)
+        let wilds = wild_values
+            .chars()
+            .fold(Wilds::jokers(jokers), |wilds, value| {
+                let value =
+                    parse_value(value).unwrap_or_else(|| panic!("Invalid wild value: {}", value));
+                wilds.with_wild_value(value)
+            });
+        if board_vec.len() == 5 && hand_known {
+            println!("Best hand: {}", evaluate_wild(&hand_vec, &board_vec, wilds));
         }
-        (
-            OnePair(pair1, kicker1_1, kicker1_2, kicker1_3),
-            OnePair(pair2, kicker2_1, kicker2_2, kicker2_3),
-        ) => {
-            let cards1 = vec![pair1, kicker1_1, kicker1_2, kicker1_3];
-            let cards2 = vec![pair2, kicker2_1, kicker2_2, kicker2_3];
-            compare_cards(&cards1, &cards2)
+        for num_players in 2..=5 {
+            print_equity(
+                num_players,
+                calculate_wild(&hand_vec, &board_vec, &dead, num_players, wilds, budget),
+                None,
+            );
         }
-        (HighCard(a1, b1, c1, d1, e1), HighCard(a2, b2, c2, d2, e2)) => {
-            let cards1 = vec![a1, b1, c1, d1, e1];
-            let cards2 = vec![a2, b2, c2, d2, e2];
-            compare_cards(&cards1, &cards2)
+        return;
+    }
+
+    if let Some(hero) = matches.value_of("range") {
+        let villains: Vec<Range> = matches
+            .values_of("villain")
+            .unwrap()
+            .map(parse_range)
+            .collect();
+        let result =
+            calculate_range_vs_range(&parse_range(hero), &board_vec, &dead, &villains, budget);
+        print_equity(villains.len() + 1, result.equity, decision);
+        for combo in &result.combos {
+            println!(
+                "  {}{} ({:.2}% of deals): Win rate {:.2}%, Tie rate {:.2}%, Equity {:.2}%",
+                combo.hand[0],
+                combo.hand[1],
+                combo.frequency * 100.0,
+                combo.equity.win_rate * 100.0,
+                combo.equity.tie_rate * 100.0,
+                combo.equity.pot_share * 100.0
+            );
         }
-        (_, _) => hand1.partial_cmp(&hand2).unwrap() as i32,
+        return;
     }
-}
 
-fn compare_cards(cards1: &[u8], cards2: &[u8]) -> i32 {
-    let mut iter1 = cards1.iter();
-    let mut iter2 = cards2.iter();
-
-    loop {
-        match (iter1.next(), iter2.next()) {
-            (Some(card1), Some(card2)) => {
-                let cmp = card1.cmp(card2);
-                if cmp != std::cmp::Ordering::Equal {
-                    return cmp as i32;
-                }
-            }
-            (None, None) => return 0,
-            (Some(_), None) => return 1,
-            (None, Some(_)) => return -1,
+    if let Some(inputs) = matches.values_of("villain") {
+        if hand_vec.len() != 2 {
+            panic!(
+                "Invalid hand length: expected 2 cards, found {}",
+                hand_vec.len()
+            );
+        }
+        let inputs: Vec<&str> = inputs.collect();
+        let villains: Vec<Range> = inputs.iter().map(|input| parse_range(input)).collect();
+        if board_vec.len() == 5 && hand_known {
+            print_best_hand(Game::Holdem.best_hand(&hand_vec, &board_vec));
         }
-    }
-}
-
-fn simulate_poker_hand(hand: [Card; 2], board: Vec<Card>, num_players: usize) -> (f64, f64, f64) {
-    let total_simulations = 1000000;
-
-    let (total_wins, total_ties, total_losses) = (0..total_simulations)
-        .into_par_iter()
-        .map(|_| {
-            let mut deck = create_deck();
-            remove_known_cards(&mut deck, &hand, &board);
-            deck.shuffle(&mut rand::thread_rng());
-            let mut all_hands = vec![hand.clone()];
-            let mut simulated_board = board.clone();
-
-            for _ in 0..num_players - 1 {
-                all_hands.push([deck.pop().unwrap(), deck.pop().unwrap()]);
-            }
 
-            while simulated_board.len() < 5 {
-                simulated_board.push(deck.pop().unwrap());
+        // With every hand known, the table gives each player's chances
+        if hand_known && villains.iter().all(|range| range.len() == 1) {
+            let hands: Vec<Vec<Card>> = [hand_vec.clone()]
+                .into_iter()
+                .chain(
+                    villains
+                        .iter()
+                        .zip(&inputs)
+                        .map(|(range, input)| as_typed(range.combos()[0], input)),
+                )
+                .collect();
+            let mut in_play: Vec<Card> = [&hand_vec[..], &board_vec, &dead].concat();
+            for (seat, hand) in hands.iter().enumerate().skip(1) {
+                if let Some(card) = hand.iter().find(|card| in_play.contains(card)) {
+                    panic!(
+                        "Villain {} ({}) holds {}, which is already in play",
+                        seat,
+                        inputs[seat - 1].trim(),
+                        card
+                    );
+                }
+                in_play.extend(hand);
             }
-
-            let player_rank = evaluate_hand(&hand, &simulated_board);
-
-            let mut definitively_loses = false;
-            let mut has_tie = false;
-
-            for other_hand in all_hands.iter().skip(1) {
-                let other_rank = evaluate_hand(other_hand, &simulated_board);
-                let comparison_result = compare_hands(player_rank, other_rank);
-
-                if comparison_result == -1 {
-                    definitively_loses = true;
-                    break;
-                } else if comparison_result == 0 {
-                    has_tie = true;
+            let seats = calculate_all_in(Game::Holdem, &hands, &board_vec, &dead, budget);
+            for (seat, (hand, equity)) in hands.iter().zip(seats).enumerate() {
+                let label = if equity.exact { "Exact" } else { "Simulated" };
+                println!(
+                    "Player {}: {} Win rate: {}, {} Tie rate: {}, Equity: {:.2}%",
+                    format_hold(hand),
+                    label,
+                    format_rate(equity.win_rate, equity.win_interval(), equity.exact),
+                    label,
+                    format_rate(equity.tie_rate, equity.tie_interval(), equity.exact),
+                    equity.pot_share * 100.0
+                );
+                // The decision is the hero's, who is in the first seat
+                if let (0, Some(decision)) = (seat, decision) {
+                    print_decision(hands.len(), equity.pot_share, decision);
                 }
             }
-
-            if definitively_loses {
-                (0, 0, 1)
-            } else if has_tie {
-                (0, 1, 0)
-            } else {
-                (1, 0, 0)
+            return;
+        }
+        let equity = calculate_range_equity(&hand_vec, &board_vec, &dead, &villains, budget);
+        print_equity(villains.len() + 1, equity, decision);
+        return;
+    }
+
+    let game = match matches.value_of("game").unwrap() {
+        "omaha" => {
+            if !(4..=6).contains(&hand_vec.len()) {
+                panic!(
+                    "Invalid hand length: expected 4 to 6 cards, found {}",
+                    hand_vec.len()
+                );
             }
-        })
-        .reduce(
-            || (0, 0, 0),
-            |(wins_a, ties_a, losses_a), (wins_b, ties_b, losses_b)| {
-                (wins_a + wins_b, ties_a + ties_b, losses_a + losses_b)
-            },
-        );
-
-    let win_rate = total_wins as f64 / total_simulations as f64;
-    let tie_rate = total_ties as f64 / total_simulations as f64;
-    let loss_rate = total_losses as f64 / total_simulations as f64;
-
-    (win_rate, tie_rate, loss_rate)
-}
-
-fn remove_known_cards(deck: &mut Vec<Card>, hand: &[Card; 2], board: &Vec<Card>) {
-    deck.retain(|card| !hand.contains(card) && !board.contains(card));
-}
-
-fn create_deck() -> Vec<Card> {
-    let mut deck = Vec::new();
-    for suit in 0..4 {
-        for value in 2..=14 {
-            deck.push(Card { value, suit });
+            Game::Omaha(hand_vec.len())
         }
-    }
-    deck
-}
-
-fn check_flush(cards: &[Card]) -> Option<Vec<Card>> {
-    let mut suits = HashMap::new();
+        _ => {
+            if hand_vec.len() != 2 {
+                panic!(
+                    "Invalid hand length: expected 2 cards, found {}",
+                    hand_vec.len()
+                );
+            }
+            Game::Holdem
+        }
+    };
 
-    for card in cards {
-        *suits.entry(card.suit).or_insert(0) += 1;
+    if board_vec.len() == 5 && hand_known {
+        print_best_hand(game.best_hand(&hand_vec, &board_vec));
     }
 
-    let flush_suit = suits.into_iter().find(|(_, count)| *count >= 5)?;
-    let mut flush_cards: Vec<Card> = cards
-        .iter()
-        .filter(|card| card.suit == flush_suit.0)
-        .cloned()
-        .collect();
+    for num_players in 2..=5 {
+        if matches.is_present("hi-lo") {
+            let result = calculate_hi_lo(game, &hand_vec, &board_vec, &dead, num_players, budget);
+
+            println!(
+                "Number of players: {}. Scoop: {}, High only: {:.2}%, Low only: {:.2}%, Quartered: {:.2}%, Lose: {}, Equity: {:.2}%",
+                num_players,
+                format_rate(result.scoop, result.interval(result.scoop), result.exact),
+                result.high_only * 100.0,
+                result.low_only * 100.0,
+                result.quartered * 100.0,
+                format_rate(result.lose, result.interval(result.lose), result.exact),
+                result.equity * 100.0
+            );
+            continue;
+        }
 
-    if flush_cards.len() >= 5 {
-        flush_cards.sort_by(|a, b| b.value.cmp(&a.value));
-        Some(flush_cards)
-    } else {
-        None
+        print_equity(
+            num_players,
+            calculate_equity(game, &hand_vec, &board_vec, &dead, num_players, budget),
+            decision,
+        );
     }
 }
 
-fn check_straight(cards: &[Card]) -> Option<Vec<u8>> {
-    if cards.len() < 5 {
-        return None;
-    }
-
-    let mut values = cards.iter().map(|card| card.value).collect::<Vec<u8>>();
-    values.sort_unstable();
-    values.dedup();
-
-    let has_high_ace = values.contains(&14);
-    if has_high_ace {
-        values.insert(0, 1);
-    }
-
-    let mut consecutive_count = 1;
-    let mut straight_values = Vec::new();
-
-    for i in 0..values.len() - 1 {
-        if values[i] + 1 == values[i + 1] {
-            consecutive_count += 1;
-            if consecutive_count >= 5 {
-                straight_values.push(values[i + 1]);
-            }
-        } else {
-            consecutive_count = 1;
-        }
-    }
-
-    if straight_values.len() > 0 {
-        straight_values.sort_by(|a, b| b.cmp(&a));
-        Some(straight_values)
+// A known villain hand in the order its cards were typed, where a range holds the high card
+// first
+fn as_typed(hand: [Card; 2], input: &str) -> Vec<Card> {
+    let typed: String = input.split_whitespace().collect();
+    if typed.starts_with(&hand[1].to_string()) {
+        vec![hand[1], hand[0]]
     } else {
-        None
+        hand.to_vec()
     }
 }
 
-fn check_multiples(cards: &[Card]) -> (Option<u8>, Vec<u8>, Vec<u8>, Vec<u8>) {
-    let mut counts = HashMap::new();
-
-    for card in cards {
-        *counts.entry(card.value).or_insert(0) += 1;
-    }
-
-    let mut four = None;
-    let mut three = Vec::new();
-    let mut pairs = Vec::new();
-    let mut singles = Vec::new();
-
-    for (value, count) in counts {
-        match count {
-            4 => four = Some(value),
-            3 => three.push(value),
-            2 => pairs.push(value),
-            1 => singles.push(value),
-            _ => unreachable!(),
-        }
+// Stops on a `??` card in a game that cannot deal one at random
+fn reject_unknown(cards: &[Card], game: &str) {
+    if cards.iter().any(|card| card.is_unknown()) {
+        panic!("Unknown cards are not supported in {}", game);
     }
-
-    three.sort_unstable_by(|a, b| b.cmp(a));
-    pairs.sort_unstable_by(|a, b| b.cmp(a));
-    singles.sort_unstable_by(|a, b| b.cmp(a));
-
-    (four, three, pairs, singles)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_straight_exists() {
-        let cards = vec![
-            Card { value: 2, suit: 0 },
-            Card { value: 3, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 6, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![6]));
-    }
-
-    #[test]
-    fn test_no_straight() {
-        let cards = vec![
-            Card { value: 2, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 6, suit: 0 },
-            Card { value: 8, suit: 0 },
-            Card { value: 10, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), None);
-    }
-
-    #[test]
-    fn test_straight_with_duplicates() {
-        let cards = vec![
-            Card { value: 3, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 6, suit: 0 },
-            Card { value: 7, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![7]));
-    }
-
-    #[test]
-    fn test_straight_with_ace_high() {
-        let cards = vec![
-            Card { value: 10, suit: 0 },
-            Card { value: 11, suit: 0 },
-            Card { value: 12, suit: 0 },
-            Card { value: 13, suit: 0 },
-            Card { value: 14, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![14]));
-    }
-
-    #[test]
-    fn test_straight_with_ace_low() {
-        let cards = vec![
-            Card { value: 2, suit: 0 },
-            Card { value: 3, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 14, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![5]));
-    }
-
-    #[test]
-    fn test_straight_seven_cards() {
-        let cards = vec![
-            Card { value: 2, suit: 0 },
-            Card { value: 3, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 6, suit: 0 },
-            Card { value: 7, suit: 0 },
-            Card { value: 8, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![8, 7, 6]));
-    }
-
-    #[test]
-    fn test_straight_seven_non_continuous_cards() {
-        let cards = vec![
-            Card { value: 2, suit: 0 },
-            Card { value: 3, suit: 0 },
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 6, suit: 0 },
-            Card { value: 8, suit: 0 },
-            Card { value: 9, suit: 0 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![6]));
-    }
-
-    #[test]
-    fn test_no_pairs_threes_or_fours() {
-        let cards = [
-            Card { value: 2, suit: 0 },
-            Card { value: 4, suit: 1 },
-            Card { value: 6, suit: 2 },
-            Card { value: 8, suit: 3 },
-            Card { value: 10, suit: 0 },
-        ];
-        assert_eq!(
-            check_multiples(&cards),
-            (None, vec![], vec![], vec![10, 8, 6, 4, 2])
-        );
-    }
-
-    #[test]
-    fn test_one_pair() {
-        let cards = [
-            Card { value: 3, suit: 0 },
-            Card { value: 3, suit: 1 },
-            Card { value: 6, suit: 2 },
-            Card { value: 8, suit: 3 },
-            Card { value: 10, suit: 0 },
-        ];
-        assert_eq!(
-            check_multiples(&cards),
-            (None, vec![], vec![3], vec![10, 8, 6])
-        );
+// The held cards, or "nothing" when drawing five new ones
+fn format_hold(hold: &[Card]) -> String {
+    if hold.is_empty() {
+        return "nothing".to_string();
     }
+    let cards: Vec<String> = hold.iter().map(|card| card.to_string()).collect();
+    cards.join(" ")
+}
 
-    #[test]
-    fn test_two_pairs() {
-        let cards = [
-            Card { value: 5, suit: 0 },
-            Card { value: 5, suit: 1 },
-            Card { value: 6, suit: 2 },
-            Card { value: 6, suit: 3 },
-            Card { value: 10, suit: 0 },
-        ];
-        assert_eq!(
-            check_multiples(&cards),
-            (None, vec![], vec![6, 5], vec![10])
+fn print_discard_options(options: &[DiscardOption]) {
+    for DiscardOption { hold, equity } in options {
+        println!(
+            "  Hold {}: Win rate {}, Tie rate {}, Equity {:.2}%",
+            format_hold(hold),
+            format_rate(equity.win_rate, equity.win_interval(), equity.exact),
+            format_rate(equity.tie_rate, equity.tie_interval(), equity.exact),
+            equity.pot_share * 100.0
         );
     }
+}
 
-    #[test]
-    fn test_three_pairs() {
-        let cards = [
-            Card { value: 7, suit: 0 },
-            Card { value: 7, suit: 1 },
-            Card { value: 5, suit: 2 },
-            Card { value: 5, suit: 3 },
-            Card { value: 6, suit: 0 },
-            Card { value: 6, suit: 1 },
-        ];
-        assert_eq!(
-            check_multiples(&cards),
-            (None, vec![], vec![7, 6, 5], vec![])
-        );
-    }
+fn print_best_hand(best: BestHand) {
+    let cards: Vec<String> = best.cards.iter().map(|card| card.to_string()).collect();
+    println!("Best hand: {} ({})", cards.join(" "), best.rank);
+}
 
-    #[test]
-    fn test_three_of_a_kind() {
-        let cards = [
-            Card { value: 7, suit: 0 },
-            Card { value: 7, suit: 1 },
-            Card { value: 7, suit: 2 },
-            Card { value: 8, suit: 3 },
-            Card { value: 10, suit: 0 },
-        ];
-        assert_eq!(
-            check_multiples(&cards),
-            (None, vec![7], vec![], vec![10, 8])
-        );
-    }
+// The chips of a betting decision given on the command line
+#[derive(Clone, Copy)]
+struct Decision {
+    odds: PotOdds,
+    // A bet or raise to price besides the all-in shove
+    bet: Option<f64>,
+}
 
-    #[test]
-    fn test_double_three_of_a_kind() {
-        let cards = [
-            Card { value: 7, suit: 0 },
-            Card { value: 8, suit: 1 },
-            Card { value: 7, suit: 2 },
-            Card { value: 8, suit: 0 },
-            Card { value: 7, suit: 1 },
-            Card { value: 8, suit: 2 },
-        ];
-        assert_eq!(check_multiples(&cards), (None, vec![8, 7], vec![], vec![]));
-    }
-
-    #[test]
-    fn test_four_of_a_kind() {
-        let cards = [
-            Card { value: 9, suit: 0 },
-            Card { value: 9, suit: 1 },
-            Card { value: 9, suit: 2 },
-            Card { value: 9, suit: 3 },
-            Card { value: 10, suit: 0 },
-        ];
-        assert_eq!(check_multiples(&cards), (Some(9), vec![], vec![], vec![10]));
-    }
-
-    #[test]
-    fn test_a_pair_and_three_of_a_kind() {
-        let cards = [
-            Card { value: 2, suit: 0 },
-            Card { value: 2, suit: 1 },
-            Card { value: 2, suit: 2 },
-            Card { value: 3, suit: 3 },
-            Card { value: 3, suit: 0 },
-        ];
-        assert_eq!(check_multiples(&cards), (None, vec![2], vec![3], vec![]));
-    }
-
-    #[test]
-    fn test_full_house_1() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 3, suit: 3 },
-            Card { value: 3, suit: 0 },
-            Card { value: 3, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(3, 2));
-    }
-
-    #[test]
-    fn test_full_house_2() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 3, suit: 0 },
-            Card { value: 3, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(2, 3));
-    }
-
-    #[test]
-    fn test_full_house_3() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 3, suit: 0 },
-            Card { value: 3, suit: 1 },
-            Card { value: 4, suit: 0 },
-            Card { value: 4, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(2, 4));
-    }
-
-    #[test]
-    fn test_straight_flush() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 3, suit: 0 }];
-        let boards = [
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 6, suit: 1 },
-            Card { value: 8, suit: 0 },
-        ];
-        assert_ne!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(6));
-    }
-
-    #[test]
-    fn test_straight_flush_2() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 3, suit: 0 }];
-        let boards = [
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 6, suit: 0 },
-            Card { value: 8, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(6));
-    }
-
-    #[test]
-    fn test_compare_hands_three_1() {
-        let hand1 = HandRank::ThreeOfAKind(10, 9, 8);
-        let hand2 = HandRank::ThreeOfAKind(10, 9, 8);
-        assert_eq!(compare_hands(hand1, hand2), 0);
-    }
-
-    #[test]
-    fn test_compare_hands_three_2() {
-        let hand1 = HandRank::ThreeOfAKind(10, 9, 6);
-        let hand2 = HandRank::ThreeOfAKind(10, 8, 6);
-        assert_eq!(compare_hands(hand1, hand2), 1);
-    }
-
-    #[test]
-    fn test_compare_hands_three_3() {
-        let hand1 = HandRank::ThreeOfAKind(10, 9, 8);
-        let hand2 = HandRank::ThreeOfAKind(10, 9, 7);
-        assert_eq!(compare_hands(hand1, hand2), 1);
-    }
-
-    #[test]
-    fn test_compare_hands_flush_1() {
-        let hand1 = HandRank::Flush(10, 9, 8, 7, 4);
-        let hand2 = HandRank::Flush(10, 9, 8, 7, 5);
-        assert_eq!(compare_hands(hand1, hand2), -1);
-    }
-
-    #[test]
-    fn test_compare_hands_two_pairs_1() {
-        let hand1 = HandRank::TwoPair(10, 9, 8);
-        let hand2 = HandRank::TwoPair(10, 9, 7);
-        assert_eq!(compare_hands(hand1, hand2), 1);
-    }
-
-    #[test]
-    fn test_compare_hands_one_pairs_1() {
-        let hand1 = HandRank::OnePair(10, 9, 8, 7);
-        let hand2 = HandRank::OnePair(10, 9, 8, 6);
-        assert_eq!(compare_hands(hand1, hand2), 1);
-    }
-
-    #[test]
-    fn test_compare_hands_tie_1() {
-        let hand1 = HandRank::Flush(10, 9, 8, 7, 4);
-        let hand2 = HandRank::Flush(10, 9, 8, 7, 4);
-        assert_eq!(compare_hands(hand1, hand2), 0);
-    }
-
-    #[test]
-    fn test_compare_hands_tie_2() {
-        let hand1 = HandRank::HighCard(10, 9, 8, 7, 4);
-        let hand2 = HandRank::HighCard(10, 9, 8, 7, 4);
-        assert_eq!(compare_hands(hand1, hand2), 0);
-    }
-
-    #[test]
-    fn test_four_of_a_kind_1() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 2, suit: 3 },
-            Card { value: 9, suit: 0 },
-            Card { value: 8, suit: 0 },
-            Card { value: 8, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 9));
-    }
-
-    #[test]
-    fn test_four_of_a_kind_2() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 2, suit: 3 },
-            Card { value: 6, suit: 0 },
-            Card { value: 8, suit: 0 },
-            Card { value: 8, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 8));
-    }
-
-    #[test]
-    fn test_four_of_a_kind_3() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 2, suit: 3 },
-            Card { value: 7, suit: 0 },
-            Card { value: 7, suit: 1 },
-            Card { value: 7, suit: 2 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 7));
-    }
-
-    #[test]
-    fn test_four_of_a_kind_4() {
-        let cards = [Card { value: 2, suit: 0 }, Card { value: 2, suit: 1 }];
-        let boards = [
-            Card { value: 2, suit: 2 },
-            Card { value: 2, suit: 3 },
-            Card { value: 6, suit: 0 },
-            Card { value: 10, suit: 1 },
-            Card { value: 5, suit: 2 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FourOfAKind(2, 10));
-    }
-
-    #[test]
-    fn test_straight_ace_both_high_and_low() {
-        let cards = vec![
-            Card { value: 10, suit: 0 },
-            Card { value: 11, suit: 1 },
-            Card { value: 12, suit: 2 },
-            Card { value: 13, suit: 3 },
-            Card { value: 14, suit: 0 },
-            Card { value: 2, suit: 1 },
-        ];
-        assert_eq!(check_straight(&cards), Some(vec![14]));
-    }
-
-    #[test]
-    fn test_two_three_of_a_kinds() {
-        let cards = [
-            Card { value: 6, suit: 0 },
-            Card { value: 6, suit: 1 },
-            Card { value: 6, suit: 2 },
-            Card { value: 8, suit: 3 },
-            Card { value: 8, suit: 0 },
-            Card { value: 8, suit: 1 },
-        ];
-        assert_eq!(check_multiples(&cards), (None, vec![8, 6], vec![], vec![]));
-    }
-
-    #[test]
-    fn test_straight_and_one_pair() {
-        let cards = [
-            Card { value: 2, suit: 0 },
-            Card { value: 3, suit: 1 },
-            Card { value: 4, suit: 2 },
-            Card { value: 5, suit: 3 },
-            Card { value: 6, suit: 0 },
-            Card { value: 6, suit: 2 },
-        ];
-        assert_eq!(
-            check_multiples(&cards),
-            (None, vec![], vec![6], vec![5, 4, 3, 2])
-        );
+fn print_equity(num_players: usize, equity: Equity, decision: Option<Decision>) {
+    let label = if equity.exact { "Exact" } else { "Simulated" };
+    let line = format!(
+        "Number of players: {}. {} Win rate: {}, {} Tie rate: {}, Equity: {:.2}%",
+        num_players,
+        label,
+        format_rate(equity.win_rate, equity.win_interval(), equity.exact),
+        label,
+        format_rate(equity.tie_rate, equity.tie_interval(), equity.exact),
+        equity.pot_share * 100.0
+    );
+    // The decision replaces the EV of a bet of 1 called by everyone
+    match decision {
+        Some(_) => println!("{}", line),
+        None => println!("{}, EV 1$ bet {:.2}$", line, equity.bet_ev(num_players)),
+    }
+    print_splits(num_players, equity);
+    if let Some(decision) = decision {
+        print_decision(num_players, equity.pot_share, decision);
     }
+}
 
-    #[test]
-    fn test_full_house_tiebreaker() {
-        let cards1 = [Card { value: 2, suit: 0 }, Card { value: 5, suit: 1 }];
-        let cards2 = [Card { value: 3, suit: 0 }, Card { value: 4, suit: 0 }];
-
-        let boards = [
-            Card { value: 2, suit: 1 },
-            Card { value: 2, suit: 2 },
-            Card { value: 3, suit: 1 },
-            Card { value: 3, suit: 2 },
-        ];
-
-        assert_eq!(evaluate_hand(&cards1, &boards), HandRank::FullHouse(2, 3));
-        assert_eq!(evaluate_hand(&cards2, &boards), HandRank::FullHouse(3, 2));
-
-        assert_eq!(
-            compare_hands(
-                evaluate_hand(&cards2, &boards),
-                evaluate_hand(&cards1, &boards)
-            ),
-            1
+// The call, bet and shove open to a hand with `equity`, with what each is worth
+fn print_decision(num_players: usize, equity: f64, Decision { odds, bet }: Decision) {
+    if odds.to_call > 0.0 {
+        let ev = odds.call_ev(equity);
+        println!(
+            "  Call {}: needs {:.2}% equity, EV {:+.2} ({})",
+            odds.call_amount(),
+            odds.required_equity() * 100.0,
+            ev,
+            if ev > 0.0 { "call" } else { "fold" }
         );
     }
-
-    #[test]
-    fn test_straight_flush_ace_low() {
-        let cards = [Card { value: 14, suit: 0 }, Card { value: 3, suit: 0 }];
-        let boards = [
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 2, suit: 0 },
-            Card { value: 8, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(5));
-    }
-
-    #[test]
-    fn test_straight_ace_low() {
-        let cards = [Card { value: 14, suit: 0 }, Card { value: 3, suit: 0 }];
-        let boards = [
-            Card { value: 4, suit: 0 },
-            Card { value: 5, suit: 0 },
-            Card { value: 2, suit: 1 },
-            Card { value: 8, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::Straight(5));
-    }
-
-    #[test]
-    fn test_straight_flush_1() {
-        let cards = [Card { value: 8, suit: 0 }, Card { value: 8, suit: 1 }];
-        let boards = [
-            Card { value: 9, suit: 0 },
-            Card { value: 10, suit: 0 },
-            Card { value: 11, suit: 0 },
-            Card { value: 12, suit: 0 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(12));
-    }
-
-    #[test]
-    fn test_full_house_4() {
-        let cards = [Card { value: 5, suit: 0 }, Card { value: 5, suit: 1 }];
-        let boards = [
-            Card { value: 5, suit: 2 },
-            Card { value: 9, suit: 3 },
-            Card { value: 9, suit: 0 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(5, 9));
-    }
-
-    #[test]
-    fn test_full_house_tiebreaker_2() {
-        let cards = [Card { value: 7, suit: 0 }, Card { value: 7, suit: 1 }];
-
-        let boards = [
-            Card { value: 7, suit: 1 },
-            Card { value: 5, suit: 2 },
-            Card { value: 5, suit: 3 },
-            Card { value: 8, suit: 0 },
-            Card { value: 4, suit: 0 },
-        ];
-
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::FullHouse(7, 5));
-    }
-
-    #[test]
-    fn test_high_card_multiple_kickers() {
-        let cards = [Card { value: 3, suit: 0 }, Card { value: 5, suit: 1 }];
-        let boards = [
-            Card { value: 6, suit: 3 },
-            Card { value: 7, suit: 0 },
-            Card { value: 9, suit: 0 },
-            Card { value: 11, suit: 2 },
-            Card { value: 13, suit: 1 },
-        ];
-        assert_eq!(
-            evaluate_hand(&cards, &boards),
-            HandRank::HighCard(13, 11, 9, 7, 6)
-        );
+    if let Some(bet) = bet.filter(|&bet| bet < odds.stack) {
+        let fold_rate = odds.break_even_fold_rate(equity, bet, num_players);
+        println!("  Bet {}: {}", bet, format_fold_rate(fold_rate));
     }
-
-    #[test]
-    fn test_flush_1() {
-        let cards = [Card { value: 5, suit: 1 }, Card { value: 8, suit: 1 }];
-        let boards = [
-            Card { value: 8, suit: 2 },
-            Card { value: 14, suit: 0 },
-            Card { value: 4, suit: 1 },
-            Card { value: 14, suit: 1 },
-            Card { value: 6, suit: 1 },
-        ];
-        assert_eq!(
-            evaluate_hand(&cards, &boards),
-            HandRank::Flush(14, 8, 6, 5, 4)
-        );
+    if odds.stack.is_finite() && odds.stack > odds.to_call {
+        let fold_rate = odds.shove_fold_rate(equity, num_players);
+        println!("  Shove {}: {}", odds.stack, format_fold_rate(fold_rate));
     }
+}
 
-    #[test]
-    fn test_straight_flush_3() {
-        let cards = [Card { value: 8, suit: 1 }, Card { value: 8, suit: 2 }];
-        let boards = [
-            Card { value: 10, suit: 1 },
-            Card { value: 11, suit: 1 },
-            Card { value: 12, suit: 1 },
-            Card { value: 9, suit: 1 },
-            Card { value: 13, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(13));
-    }
-
-    #[test]
-    fn test_royal_flush() {
-        let cards = [Card { value: 9, suit: 1 }, Card { value: 9, suit: 2 }];
-        let boards = [
-            Card { value: 10, suit: 1 },
-            Card { value: 11, suit: 1 },
-            Card { value: 12, suit: 1 },
-            Card { value: 13, suit: 1 },
-            Card { value: 14, suit: 0 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::StraightFlush(13));
-    }
-
-    #[test]
-    fn test_royal_flush_2() {
-        let cards = [Card { value: 9, suit: 1 }, Card { value: 9, suit: 2 }];
-        let boards = [
-            Card { value: 10, suit: 1 },
-            Card { value: 11, suit: 1 },
-            Card { value: 12, suit: 1 },
-            Card { value: 13, suit: 1 },
-            Card { value: 14, suit: 1 },
-        ];
-        assert_eq!(evaluate_hand(&cards, &boards), HandRank::RoyalFlush);
+// How often everyone has to fold for a bet to break even, unless it wins when called
+fn format_fold_rate(fold_rate: f64) -> String {
+    if fold_rate == 0.0 {
+        "profitable even when called".to_string()
+    } else {
+        format!(
+            "breaks even when everyone folds {:.2}% of the time",
+            fold_rate * 100.0
+        )
     }
 }
 
-fn parse_cards(input: &str) -> Vec<Card> {
-    input
-        .split_whitespace()
-        .filter_map(|card_str| {
-            if card_str.len() != 2 {
-                None
-            } else {
-                let bytes = card_str.as_bytes();
-                let value = match bytes[0] as char {
-                    '2'..='9' => bytes[0] as u8 - b'0',
-                    'T' => 10,
-                    'J' => 11,
-                    'Q' => 12,
-                    'K' => 13,
-                    'A' => 14,
-                    _ => panic!("Invalid card value"),
-                };
-                let suit = match bytes[1] as char {
-                    'h' => 0,
-                    'd' => 1,
-                    'c' => 2,
-                    's' => 3,
-                    _ => panic!("Invalid card suit"),
-                };
-                Some(Card { value, suit })
-            }
-        })
-        .collect()
+// The tie rate broken down by how many players split the pot, when there are ties
+fn print_splits(num_players: usize, equity: Equity) {
+    let splits: Vec<String> = (2..=num_players)
+        .filter(|&players| equity.split_rate(players) > 0.0)
+        .map(|players| format!("{}-way {:.2}%", players, equity.split_rate(players) * 100.0))
+        .collect();
+    if !splits.is_empty() {
+        println!("  Ties: {}", splits.join(", "));
+    }
 }
 
-fn main() {
-    let matches = Command::new("Poker Hand Simulator")
-        .version("1.0")
-        .author("Your Name")
-        .about("Simulates a poker hand")
-        .arg(
-            Arg::new("hand")
-                .short('h')
-                .long("hand")
-                .value_name("HAND")
-                .help("Sets the hand to evaluate")
-                .takes_value(true)
-                .required(true),
-        )
-        .arg(
-            Arg::new("board")
-                .short('b')
-                .long("board")
-                .value_name("BOARD")
-                .help("Sets the board cards")
-                .takes_value(true)
-                .default_value(""),
+// A rate as a percentage, followed by its 95% confidence interval when simulated
+fn format_rate(rate: f64, (low, high): (f64, f64), exact: bool) -> String {
+    if exact {
+        format!("{:.2}%", rate * 100.0)
+    } else {
+        format!(
+            "{:.2}% (95% CI {:.2}-{:.2}%)",
+            rate * 100.0,
+            low * 100.0,
+            high * 100.0
         )
-        .get_matches();
-
-    let hand_input = matches.value_of("hand").unwrap();
-    let board_input = matches.value_of("board").unwrap();
-
-    let hand_vec = parse_cards(hand_input);
-    let board_vec = parse_cards(board_input);
-
-    if hand_vec.len() != 2 {
-        panic!(
-            "Invalid hand length: expected 2 cards, found {}",
-            hand_vec.len()
-        );
-    }
-
-    let hand_array = [hand_vec[0], hand_vec[1]];
-
-    for num_players in 2..=5 {
-        let (win_rate, tie_rate, _) =
-            simulate_poker_hand(hand_array, board_vec.clone(), num_players);
-
-        println!(
-            "Number of players: {}. Simulated Win rate: {:.2}%, Simulated Tie rate: {:.2}%, EV 1$ bet {:.2}$",
-            num_players,
-            win_rate * 100.0,
-            tie_rate * 100.0,
-            num_players as f64 * win_rate + tie_rate - 1.0
-        );
     }
 }
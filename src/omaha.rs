@@ -0,0 +1,226 @@
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::evaluator::{order_best_hand, BestHand};
+use crate::lookup::{evaluate_set, HandStrength};
+use crate::low::{eight_or_better_mask, low_from_mask, LowRank};
+
+/// Evaluates an Omaha hand, which must use exactly two of the `hole` cards and exactly
+/// three of the `board` cards.
+///
+/// # Panics
+///
+/// Panics unless there are 4 to 6 hole cards and 3 to 5 board cards.
+pub fn evaluate_omaha(hole: &[Card], board: &[Card]) -> HandStrength {
+    check_omaha_lengths(hole, board);
+
+    // At most 10 ways to pick three board cards, reused for every pair of hole cards
+    let mut triples = [CardSet::EMPTY; 10];
+    let mut triple_count = 0;
+    for a in 0..board.len() {
+        for b in a + 1..board.len() {
+            for c in b + 1..board.len() {
+                triples[triple_count] = [board[a], board[b], board[c]].iter().collect();
+                triple_count += 1;
+            }
+        }
+    }
+
+    let mut best = None;
+    for i in 0..hole.len() {
+        for j in i + 1..hole.len() {
+            let pair: CardSet = [hole[i], hole[j]].iter().collect();
+            for &triple in &triples[..triple_count] {
+                best = best.max(Some(evaluate_set(pair | triple)));
+            }
+        }
+    }
+    best.unwrap()
+}
+
+/// Like [`evaluate_omaha`], but also returns the five cards that make the best hand.
+pub fn evaluate_best_omaha_hand(hole: &[Card], board: &[Card]) -> BestHand {
+    let (strength, cards) = best_omaha_combination(hole, board);
+    order_best_hand(strength.rank(), cards)
+}
+
+/// Evaluates the eight-or-better low of an Omaha hand, using exactly two of the `hole`
+/// cards and exactly three of the `board` cards.
+///
+/// Returns `None` if no qualifying low can be made that way.
+///
+/// # Panics
+///
+/// Panics unless there are 4 to 6 hole cards and 3 to 5 board cards.
+pub fn evaluate_omaha_low(hole: &[Card], board: &[Card]) -> Option<LowRank> {
+    check_omaha_lengths(hole, board);
+
+    let mut best = None;
+    for i in 0..hole.len() {
+        for j in i + 1..hole.len() {
+            let pair = eight_or_better_mask(&[hole[i], hole[j]]);
+            if pair.count_ones() != 2 {
+                continue;
+            }
+            for a in 0..board.len() {
+                for b in a + 1..board.len() {
+                    for c in b + 1..board.len() {
+                        let triple = eight_or_better_mask(&[board[a], board[b], board[c]]);
+                        if triple.count_ones() == 3 && pair & triple == 0 {
+                            best = best.max(Some(low_from_mask(pair | triple)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+fn best_omaha_combination(hole: &[Card], board: &[Card]) -> (HandStrength, [Card; 5]) {
+    check_omaha_lengths(hole, board);
+
+    let mut best: Option<(HandStrength, [Card; 5])> = None;
+    for i in 0..hole.len() {
+        for j in i + 1..hole.len() {
+            for a in 0..board.len() {
+                for b in a + 1..board.len() {
+                    for c in b + 1..board.len() {
+                        let cards = [hole[i], hole[j], board[a], board[b], board[c]];
+                        let strength = evaluate_set(cards.iter().collect());
+                        if best.is_none_or(|(best_strength, _)| strength > best_strength) {
+                            best = Some((strength, cards));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    best.unwrap()
+}
+
+fn check_omaha_lengths(hole: &[Card], board: &[Card]) {
+    assert!(
+        (4..=6).contains(&hole.len()),
+        "Invalid Omaha hand length: expected 4 to 6 cards, found {}",
+        hole.len()
+    );
+    assert!(
+        (3..=5).contains(&board.len()),
+        "Invalid Omaha board length: expected 3 to 5 cards, found {}",
+        board.len()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use crate::evaluator::HandRank;
+
+    fn rank(hole: &str, board: &str) -> HandRank {
+        evaluate_omaha(&parse_cards(hole), &parse_cards(board)).rank()
+    }
+
+    #[test]
+    fn test_one_suited_hole_card_is_no_flush() {
+        assert_eq!(
+            rank("Ah Kd Qc Js", "2h 5h 8h 9h Th"),
+            HandRank::Straight(12)
+        );
+    }
+
+    #[test]
+    fn test_two_suited_hole_cards_make_a_flush() {
+        assert_eq!(
+            rank("Ah 3h Qc Js", "2h 5h 8h 9d Tc"),
+            HandRank::Flush(14, 8, 5, 3, 2)
+        );
+    }
+
+    #[test]
+    fn test_four_of_a_kind_in_hand_is_one_pair() {
+        assert_eq!(
+            rank("Ah Ad Ac As", "2h 5d 8c 9s Jh"),
+            HandRank::OnePair(14, 11, 9, 8)
+        );
+    }
+
+    #[test]
+    fn test_board_trips_need_hole_pair_for_full_house() {
+        assert_eq!(
+            rank("Ah Kd Qc Js", "9h 9d 9c 5s 2h"),
+            HandRank::ThreeOfAKind(9, 14, 13)
+        );
+        assert_eq!(
+            rank("Ah Ad Qc Js", "9h 9d 9c 5s 2h"),
+            HandRank::FullHouse(9, 14)
+        );
+    }
+
+    #[test]
+    fn test_five_and_six_card_omaha() {
+        assert_eq!(
+            rank("Ah Kd Qc Js Th", "9h 3h 4h 5s 2d"),
+            HandRank::Flush(14, 10, 9, 4, 3)
+        );
+        assert_eq!(
+            rank("Ah Kd Qc Js Th 6c", "9h 3h 4h 5s 2d"),
+            HandRank::Flush(14, 10, 9, 4, 3)
+        );
+        assert_eq!(
+            rank("Ah Kd Qc Js Tc 6c", "9h 3h 4h 5s 2d"),
+            HandRank::HighCard(14, 13, 9, 5, 4)
+        );
+    }
+
+    #[test]
+    fn test_flop_only() {
+        assert_eq!(
+            rank("Ah Ad Kc Ks", "Ac 7d 2h"),
+            HandRank::ThreeOfAKind(14, 7, 2)
+        );
+    }
+
+    #[test]
+    fn test_best_omaha_hand_cards() {
+        let best =
+            evaluate_best_omaha_hand(&parse_cards("Ah Ad Qc Js"), &parse_cards("9h 9d 9c 5s 2h"));
+        assert_eq!(best.rank, HandRank::FullHouse(9, 14));
+        assert_eq!(best.cards.to_vec(), parse_cards("9h 9d 9c Ah Ad"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Omaha hand length")]
+    fn test_holdem_hand_is_rejected() {
+        evaluate_omaha(&parse_cards("Ah Ad"), &parse_cards("9h 9d 9c"));
+    }
+
+    fn low(hole: &str, board: &str) -> Option<String> {
+        evaluate_omaha_low(&parse_cards(hole), &parse_cards(board)).map(|low| low.to_string())
+    }
+
+    #[test]
+    fn test_omaha_low_uses_two_hole_cards() {
+        assert_eq!(
+            low("Ah 2d Kc Ks", "3h 4d 5c Kd Qh"),
+            Some("5-4-3-2-A".to_string())
+        );
+        // Only one low card in the hand
+        assert_eq!(low("Ah Kd Kc Ks", "2h 3d 4c 5d 8h"), None);
+        // Four low cards on the board are not enough on their own
+        assert_eq!(low("Ah 2d Kc Ks", "2h 3d Qc Qd Jh"), None);
+    }
+
+    #[test]
+    fn test_omaha_low_counterfeit() {
+        // The board pairs the hero's deuce, so the best low uses the three instead
+        assert_eq!(
+            low("Ah 2d 3c Ks", "2h 6d 7c Kd 8h"),
+            Some("7-6-3-2-A".to_string())
+        );
+        assert_eq!(
+            low("Ah 2d 9c Ks", "2h 6d 7c Kd 8h"),
+            Some("8-7-6-2-A".to_string())
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use crate::card::{check_known, Card};
+use crate::card_set::CardSet;
+use crate::draw::{sort_by_equity, DiscardOption};
+use crate::lookup::evaluate_strength;
+use crate::simulation::{
+    check_board_length, check_player_count, distinct_cards, seat_error, split_showdown,
+    tally_deals, tally_equity, Budget, Deal,
+};
+
+/// When the players of a Pineapple game throw away their third hole card.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Discard {
+    // Before the flop, as in Pineapple
+    Preflop,
+    // After the flop, as in Crazy Pineapple
+    AfterFlop,
+}
+
+/// Estimates the hero's chances for each of the three discards from a three card Pineapple
+/// `hand` on a partial `board`, against `num_players - 1` random opponents.
+///
+/// Opponents are dealt three cards and throw one away at the `discard` point. Before the
+/// flop they keep a pair if they have one, and otherwise their two highest cards, suited
+/// ones first on equal values. After the flop they keep the two cards that make the best
+/// hand with it. Discarded cards are out of play, and the two kept cards play like a
+/// Hold'em hand.
+///
+/// Each [`DiscardOption`] holds the two cards kept, and the options are ordered from the
+/// largest share of the pot down, so the first one is the recommended discard.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold exactly 3 cards, if `board` holds more than 5 cards, if
+/// `hand` or `board` holds an unknown card, or if `num_players` is not from 2 to 23 or the
+/// deck cannot complete every hand.
+pub fn simulate_pineapple(
+    hand: &[Card],
+    board: &[Card],
+    num_players: usize,
+    discard: Discard,
+) -> Vec<DiscardOption> {
+    simulate_pineapple_with_budget(hand, board, &[], num_players, discard, Budget::DEFAULT)
+}
+
+/// Like [`simulate_pineapple`], playing each discard out until `budget` is spent and never
+/// dealing the `dead` cards. With a seed every discard is played against the same random
+/// deals.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold exactly 3 cards, if `board` holds more than 5 cards, if
+/// `hand` or `board` holds an unknown card, if a card is given twice among `hand`, `board`
+/// and `dead`, or if `num_players` is not from 2 to 23 or the deck cannot complete every
+/// hand.
+pub fn simulate_pineapple_with_budget(
+    hand: &[Card],
+    board: &[Card],
+    dead: &[Card],
+    num_players: usize,
+    discard: Discard,
+    budget: Budget,
+) -> Vec<DiscardOption> {
+    assert_eq!(
+        hand.len(),
+        3,
+        "Invalid hand length: expected 3 cards, found {}",
+        hand.len()
+    );
+    check_board_length(board);
+    check_player_count(num_players);
+    check_known(hand, "Pineapple");
+    check_known(board, "Pineapple");
+    let known = distinct_cards(hand.iter().chain(board).chain(dead));
+
+    let mut options: Vec<DiscardOption> = (0..3)
+        .map(|thrown| {
+            let hold: Vec<Card> = (0..3).filter(|&i| i != thrown).map(|i| hand[i]).collect();
+            let tally = tally_deals(
+                Deal {
+                    deck: CardSet::full_deck() - known,
+                    ..Deal::new(3, hand, board, num_players)
+                },
+                budget,
+                seat_error,
+                |_, simulated_board, opponents| {
+                    split_showdown(
+                        evaluate_strength(&hold, simulated_board),
+                        opponents.chunks(3).map(|other_hand| {
+                            let kept = opponent_keeps(other_hand, simulated_board, discard);
+                            evaluate_strength(&kept, simulated_board)
+                        }),
+                    )
+                },
+            );
+            DiscardOption {
+                hold,
+                equity: tally_equity(tally),
+            }
+        })
+        .collect();
+    sort_by_equity(&mut options);
+    options
+}
+
+// The two of three hole cards an opponent keeps
+fn opponent_keeps(cards: &[Card], board: &[Card], discard: Discard) -> [Card; 2] {
+    let pairs = [
+        [cards[0], cards[1]],
+        [cards[0], cards[2]],
+        [cards[1], cards[2]],
+    ];
+    match discard {
+        Discard::Preflop => pairs
+            .into_iter()
+            .max_by_key(|[a, b]| {
+                (
+                    a.value == b.value,
+                    a.value.max(b.value),
+                    a.value.min(b.value),
+                    a.suit == b.suit,
+                )
+            })
+            .unwrap(),
+        Discard::AfterFlop => pairs
+            .into_iter()
+            .max_by_key(|pair| evaluate_strength(pair, &board[..3]))
+            .unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn keeps(cards: &str, board: &str, discard: Discard) -> Vec<Card> {
+        opponent_keeps(&parse_cards(cards), &parse_cards(board), discard).to_vec()
+    }
+
+    #[test]
+    fn test_opponent_keeps_preflop() {
+        let board = "";
+        assert_eq!(
+            keeps("7h Ad 7c", board, Discard::Preflop),
+            parse_cards("7h 7c")
+        );
+        assert_eq!(
+            keeps("Kh 2d Ah", board, Discard::Preflop),
+            parse_cards("Kh Ah")
+        );
+        assert_eq!(
+            keeps("Kh Kd Ah", board, Discard::Preflop),
+            parse_cards("Kh Kd")
+        );
+    }
+
+    #[test]
+    fn test_opponent_keeps_after_flop() {
+        let board = "9h 9d 2c";
+        assert_eq!(
+            keeps("Ah Kd 2s", board, Discard::AfterFlop),
+            parse_cards("Ah 2s")
+        );
+        assert_eq!(
+            keeps("Ah Kd 9s", board, Discard::AfterFlop),
+            parse_cards("Ah 9s")
+        );
+    }
+
+    #[test]
+    fn test_pineapple_recommends_keeping_aces() {
+        let hand = parse_cards("Ah Ad 7c");
+        let options = simulate_pineapple_with_budget(
+            &hand,
+            &[],
+            &[],
+            3,
+            Discard::Preflop,
+            Budget::iterations(10000),
+        );
+        assert_eq!(options.len(), 3);
+        assert_eq!(options[0].hold, parse_cards("Ah Ad"));
+        assert!(options[0].equity.pot_share > options[1].equity.pot_share);
+    }
+
+    #[test]
+    fn test_crazy_pineapple_keeps_the_trips() {
+        let hand = parse_cards("Ah Kd 9s");
+        let board = parse_cards("9h 9d 2c");
+        let options = simulate_pineapple_with_budget(
+            &hand,
+            &board,
+            &[],
+            2,
+            Discard::AfterFlop,
+            Budget::iterations(10000),
+        );
+        assert_eq!(options[2].hold, parse_cards("Ah Kd"));
+        assert!(options[1].equity.win_rate > options[2].equity.win_rate + 0.2);
+    }
+
+    #[test]
+    fn test_dead_cards_are_never_dealt() {
+        // On the river only the opponent's three cards are dealt, from 44 cards or 43 with
+        // one more out of play
+        let hand = parse_cards("Ah Kd 9s");
+        let board = parse_cards("9h 9d 2c 5s Jh");
+        let budget = Budget::iterations(100000);
+        let deals = |dead: &str| {
+            let options = simulate_pineapple_with_budget(
+                &hand,
+                &board,
+                &parse_cards(dead),
+                2,
+                Discard::AfterFlop,
+                budget,
+            );
+            assert!(options.iter().all(|option| option.equity.exact));
+            options[0].equity.deals
+        };
+        assert_eq!(deals(""), 13244);
+        assert_eq!(deals("9c"), 12341);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: Ah")]
+    fn test_duplicate_card_is_rejected() {
+        simulate_pineapple_with_budget(
+            &parse_cards("Ah Ad 7c"),
+            &parse_cards("Ah Kd 2c"),
+            &[],
+            2,
+            Discard::Preflop,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown cards are not supported in Pineapple")]
+    fn test_unknown_card_is_rejected() {
+        simulate_pineapple_with_budget(
+            &parse_cards("Ah ?? 3c"),
+            &[],
+            &[],
+            2,
+            Discard::Preflop,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid hand length")]
+    fn test_two_card_hand_is_rejected() {
+        simulate_pineapple_with_budget(
+            &parse_cards("Ah Ad"),
+            &[],
+            &[],
+            2,
+            Discard::Preflop,
+            Budget::iterations(1),
+        );
+    }
+}
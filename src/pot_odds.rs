@@ -0,0 +1,161 @@
+/// The chips in play when the hero has a betting decision, in any unit such as big blinds.
+///
+/// The hero's equity is the share of the final pot the hero expects to win, such as
+/// [`Equity::pot_share`](crate::Equity::pot_share), or heads-up the win rate plus half
+/// the tie rate from [`simulate_poker_hand`](crate::simulate_poker_hand).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PotOdds {
+    // Chips in the pot, including any bet the hero faces
+    pub pot: f64,
+    // The bet the hero faces, 0 when checked to
+    pub to_call: f64,
+    // The smaller of the hero's and the opponents' stacks behind, which caps every bet
+    pub stack: f64,
+}
+
+impl PotOdds {
+    /// Creates the decision for a `pot` with a bet of `to_call` to call and an effective
+    /// `stack`, which can be [`f64::INFINITY`] when deep enough not to matter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `pot` or `to_call` is negative, if the bet faced is larger than the pot
+    /// holding it, or if `stack` is not positive.
+    pub fn new(pot: f64, to_call: f64, stack: f64) -> PotOdds {
+        assert!(pot >= 0.0, "Invalid pot: {}", pot);
+        assert!(to_call >= 0.0, "Invalid bet to call: {}", to_call);
+        assert!(
+            to_call <= pot,
+            "Invalid bet to call: {} is more than the pot of {}",
+            to_call,
+            pot
+        );
+        assert!(stack > 0.0, "Invalid stack: {}", stack);
+        PotOdds {
+            pot,
+            to_call,
+            stack,
+        }
+    }
+
+    /// The part of the bet the hero can call, at most the effective stack.
+    pub fn call_amount(&self) -> f64 {
+        self.to_call.min(self.stack)
+    }
+
+    /// The equity a call needs to break even: the call over the pot once it is called.
+    pub fn required_equity(&self) -> f64 {
+        let call = self.call_amount();
+        if call == 0.0 {
+            return 0.0;
+        }
+        call / (self.called_pot() + call)
+    }
+
+    /// The expected profit of calling rather than folding with `equity`, assuming the call
+    /// closes the betting.
+    pub fn call_ev(&self, equity: f64) -> f64 {
+        let call = self.call_amount();
+        equity * (self.called_pot() + call) - call
+    }
+
+    /// The expected profit of betting or raising `bet` chips, capped at the stack, when each
+    /// of the `num_players - 1` opponents calls and the hero then has `equity`, or they all
+    /// fold at `fold_rate`.
+    pub fn bet_ev(&self, equity: f64, bet: f64, num_players: usize, fold_rate: f64) -> f64 {
+        let called = self.called_bet_ev(equity, bet, num_players);
+        fold_rate * self.pot + (1.0 - fold_rate) * called
+    }
+
+    /// The rate at which every opponent has to fold to a bet or raise of `bet` chips for it
+    /// to break even, as in [`PotOdds::bet_ev`]. It is 0 when the bet is profitable even
+    /// when always called.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bet` does not raise the bet faced, unless it puts the hero all-in.
+    pub fn break_even_fold_rate(&self, equity: f64, bet: f64, num_players: usize) -> f64 {
+        let called = self.called_bet_ev(equity, bet, num_players);
+        if called >= 0.0 {
+            return 0.0;
+        }
+        -called / (self.pot - called)
+    }
+
+    /// The break-even fold rate of going all-in for the effective stack.
+    pub fn shove_fold_rate(&self, equity: f64, num_players: usize) -> f64 {
+        self.break_even_fold_rate(equity, self.stack, num_players)
+    }
+
+    // Pot once the part of a bet larger than the stack is returned
+    fn called_pot(&self) -> f64 {
+        self.pot - (self.to_call - self.call_amount())
+    }
+
+    // Profit of a bet that every opponent calls, where the player who bet has to_call in
+    // the pot already and the others match the whole bet
+    fn called_bet_ev(&self, equity: f64, bet: f64, num_players: usize) -> f64 {
+        let bet = bet.min(self.stack);
+        assert!(
+            bet > self.to_call || bet == self.stack,
+            "Invalid bet: {} does not raise the bet of {}",
+            bet,
+            self.to_call
+        );
+        let callers = (num_players - 1) as f64;
+        let final_pot = self.pot - self.to_call + bet * (callers + 1.0);
+        equity * final_pot - bet
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_odds() {
+        // 50 to call into 150 needs a quarter of the 200 pot
+        let odds = PotOdds::new(150.0, 50.0, f64::INFINITY);
+        assert_eq!(odds.required_equity(), 0.25);
+        assert_eq!(odds.call_ev(0.25), 0.0);
+        assert_eq!(odds.call_ev(0.5), 50.0);
+        assert_eq!(PotOdds::new(100.0, 0.0, 100.0).required_equity(), 0.0);
+    }
+
+    #[test]
+    fn test_call_capped_by_stack() {
+        // Only 20 of the 50 bet can be called, and the other 30 go back
+        let odds = PotOdds::new(150.0, 50.0, 20.0);
+        assert_eq!(odds.call_amount(), 20.0);
+        assert_eq!(odds.required_equity(), 20.0 / 140.0);
+        assert_eq!(odds.call_ev(0.5), 50.0);
+    }
+
+    #[test]
+    fn test_break_even_fold_rate() {
+        // A pot sized bluff has to work half the time
+        let odds = PotOdds::new(100.0, 0.0, 1000.0);
+        assert_eq!(odds.break_even_fold_rate(0.0, 100.0, 2), 0.5);
+        assert_eq!(odds.bet_ev(0.0, 100.0, 2, 0.5), 0.0);
+        assert_eq!(odds.break_even_fold_rate(0.6, 100.0, 2), 0.0);
+        // Two callers give more to win when called
+        assert!(
+            odds.break_even_fold_rate(0.3, 100.0, 3) < odds.break_even_fold_rate(0.3, 100.0, 2)
+        );
+    }
+
+    #[test]
+    fn test_shove_over_a_bet() {
+        // Shoving 200 over a bet of 50 into 150: called, the pot is 100 + 200 + 200
+        let odds = PotOdds::new(150.0, 50.0, 200.0);
+        let fold_rate = odds.shove_fold_rate(0.3, 2);
+        assert!((fold_rate - 50.0 / 200.0).abs() < 1e-12);
+        assert!(odds.bet_ev(0.3, 200.0, 2, fold_rate).abs() < 1e-12);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid bet")]
+    fn test_bet_must_raise() {
+        PotOdds::new(150.0, 50.0, 200.0).break_even_fold_rate(0.3, 50.0, 2);
+    }
+}
@@ -0,0 +1,923 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::time::Instant;
+
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rayon::prelude::*;
+
+use crate::card::{known_cards, parse_cards, parse_value, Card};
+use crate::card_set::CardSet;
+use crate::enumeration::{deal_count, enumerate_deals};
+use crate::lookup::evaluate_strength;
+use crate::rng::{Entropy, RngSource, Seeded};
+use crate::simulation::{
+    add_totals, check_board_length, check_player_count, chunks, distinct_cards, seat_equity,
+    seat_error, split_showdown, Budget, Deal, Equity, SeatScores, SeatTotals, DEALT, SEAT_SCORES,
+};
+
+/// A set of two card Hold'em hands an opponent may hold, each written once with how often
+/// it is played.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Range {
+    combos: Vec<[Card; 2]>,
+    // Frequency of each hand in `combos`, from 0 to 1
+    weights: Vec<f64>,
+}
+
+// Which suit combinations a hand class such as `AKs` stands for
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Suits {
+    Suited,
+    Offsuit,
+    // Both suited and offsuit, or any pair
+    Any,
+}
+
+// A hand class such as `AKs` or `TT`, with the higher value first
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct HandClass {
+    high: u8,
+    low: u8,
+    suits: Suits,
+}
+
+impl Range {
+    /// Every two card hand.
+    pub fn any() -> Range {
+        let deck: Vec<Card> = CardSet::full_deck().iter().collect();
+        let mut combos = Vec::new();
+        for (i, &first) in deck.iter().enumerate() {
+            for &second in &deck[i + 1..] {
+                combos.push([first, second]);
+            }
+        }
+        let weights = vec![1.0; combos.len()];
+        Range { combos, weights }
+    }
+
+    /// The hands in the range.
+    pub fn combos(&self) -> &[[Card; 2]] {
+        &self.combos
+    }
+
+    /// How often each hand of [`Range::combos`] is played, from 0 to 1.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// The number of hands in the range.
+    pub fn len(&self) -> usize {
+        self.combos.len()
+    }
+
+    /// Whether the range holds no hand.
+    pub fn is_empty(&self) -> bool {
+        self.combos.is_empty()
+    }
+
+    /// The hands that hold none of the `dead` cards, such as the hero's hand and the board.
+    pub fn without(&self, dead: CardSet) -> Range {
+        let (combos, weights) = self
+            .combos
+            .iter()
+            .zip(&self.weights)
+            .filter(|(combo, _)| !combo.iter().any(|&card| dead.contains(card)))
+            .unzip();
+        Range { combos, weights }
+    }
+}
+
+/// Parses a comma separated hand range such as `"TT+, AKs, A5s-A2s:0.5, KQo, JhTh"`.
+///
+/// Each entry is one of:
+///
+/// - a pair such as `TT`, or suited `AKs`, offsuit `AKo` or both `AK`;
+/// - one of those with `+`, adding every higher pair, or every higher second card up to
+///   one below the first, so `ATs+` is `ATs, AJs, AQs, AKs`;
+/// - a span such as `A5s-A2s` or `99-66`, sharing the first card or both being pairs;
+/// - one exact hand such as `JhTh` or `Jh Th`, where `??` stands for any card, so `Ah??`
+///   is every hand holding the ace of hearts.
+///
+/// Any entry can end with `:` and the frequency its hands are played at, from 0 to 1, so
+/// `AKs:0.5` holds suited ace king half of the time. Entries without one are always played.
+///
+/// Hands given by more than one entry are only counted once, at the frequency of the first,
+/// and the hands are kept in order from the highest values down.
+///
+/// # Panics
+///
+/// Panics if an entry does not follow this syntax.
+pub fn parse_range(input: &str) -> Range {
+    let mut seen = HashSet::new();
+    let mut weighted = Vec::new();
+    for entry in input
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+    {
+        let (hands, weight) = match entry.split_once(':') {
+            Some((hands, weight)) => (hands.trim(), parse_weight(weight, entry)),
+            None => (entry, 1.0),
+        };
+        for combo in parse_entry(hands) {
+            let set: CardSet = combo.iter().collect();
+            if seen.insert(set) {
+                weighted.push((combo, weight));
+            }
+        }
+    }
+    // The same hands give the same range, however they were written
+    weighted.sort_by_key(|(combo, _)| {
+        (
+            Reverse(combo[0].value),
+            Reverse(combo[1].value),
+            combo[0].suit,
+            combo[1].suit,
+        )
+    });
+    let (combos, weights) = weighted.into_iter().unzip();
+    Range { combos, weights }
+}
+
+fn parse_weight(weight: &str, entry: &str) -> f64 {
+    match weight.trim().parse() {
+        Ok(weight) if (0.0..=1.0).contains(&weight) => weight,
+        _ => invalid(entry),
+    }
+}
+
+fn parse_entry(entry: &str) -> Vec<[Card; 2]> {
+    let entry: String = entry.split_whitespace().collect();
+    let entry = entry.as_str();
+    let chars: Vec<char> = entry.chars().collect();
+    if chars.len() == 4 && "hdcs?".contains(chars[1]) && "hdcs?".contains(chars[3]) {
+        let cards = parse_cards(&format!("{} {}", &entry[..2], &entry[2..]));
+        assert!(
+            cards[0] != cards[1] || cards[0].is_unknown(),
+            "Invalid range entry: {}",
+            entry
+        );
+        return hand_combos([cards[0], cards[1]]);
+    }
+
+    let classes = if let Some((first, last)) = entry.split_once('-') {
+        let (first, last) = (parse_class(first, entry), parse_class(last, entry));
+        assert!(first.suits == last.suits, "Invalid range entry: {}", entry);
+        if first.high == first.low {
+            assert!(last.high == last.low, "Invalid range entry: {}", entry);
+            let (low, high) = (first.low.min(last.low), first.low.max(last.low));
+            (low..=high).map(pair).collect()
+        } else {
+            assert!(
+                first.high == last.high && last.high != last.low,
+                "Invalid range entry: {}",
+                entry
+            );
+            let (low, high) = (first.low.min(last.low), first.low.max(last.low));
+            (low..=high).map(|low| HandClass { low, ..first }).collect()
+        }
+    } else if let Some(class) = entry.strip_suffix('+') {
+        let class = parse_class(class, entry);
+        if class.high == class.low {
+            (class.low..=14).map(pair).collect()
+        } else {
+            (class.low..class.high)
+                .map(|low| HandClass { low, ..class })
+                .collect()
+        }
+    } else {
+        vec![parse_class(entry, entry)]
+    };
+    classes.into_iter().flat_map(class_combos).collect()
+}
+
+// The hands matching `hand`, where a `Card::UNKNOWN` placeholder can be any other card,
+// each with the higher value first
+fn hand_combos(hand: [Card; 2]) -> Vec<[Card; 2]> {
+    let ordered = |first: Card, second: Card| {
+        if first.value < second.value {
+            [second, first]
+        } else {
+            [first, second]
+        }
+    };
+    match known_cards(&hand)[..] {
+        [first, second] => vec![ordered(first, second)],
+        [card] => CardSet::full_deck()
+            .iter()
+            .filter(|&other| other != card)
+            .map(|other| ordered(card, other))
+            .collect(),
+        _ => Range::any().combos,
+    }
+}
+
+fn pair(value: u8) -> HandClass {
+    HandClass {
+        high: value,
+        low: value,
+        suits: Suits::Any,
+    }
+}
+
+// Parses a hand class such as `AKs`, `KQo`, `AK` or `TT`, as found in `entry`
+fn parse_class(class: &str, entry: &str) -> HandClass {
+    let chars: Vec<char> = class.trim().chars().collect();
+    let (first, second, suits) = match chars[..] {
+        [first, second] => (first, second, Suits::Any),
+        [first, second, 's'] => (first, second, Suits::Suited),
+        [first, second, 'o'] => (first, second, Suits::Offsuit),
+        _ => invalid(entry),
+    };
+    let (Some(first), Some(second)) = (parse_value(first), parse_value(second)) else {
+        invalid(entry)
+    };
+    if first == second && suits != Suits::Any {
+        invalid(entry);
+    }
+    HandClass {
+        high: first.max(second),
+        low: first.min(second),
+        suits,
+    }
+}
+
+fn invalid(entry: &str) -> ! {
+    panic!("Invalid range entry: {}", entry)
+}
+
+fn class_combos(class: HandClass) -> Vec<[Card; 2]> {
+    let mut combos = Vec::new();
+    for high_suit in 0..4 {
+        for low_suit in 0..4 {
+            let suited = high_suit == low_suit;
+            let wanted = match class.suits {
+                // Each pair is made once, from the lower suit to the higher one
+                _ if class.high == class.low => high_suit < low_suit,
+                Suits::Suited => suited,
+                Suits::Offsuit => !suited,
+                Suits::Any => true,
+            };
+            if wanted {
+                combos.push([
+                    Card {
+                        value: class.high,
+                        suit: high_suit,
+                    },
+                    Card {
+                        value: class.low,
+                        suit: low_suit,
+                    },
+                ]);
+            }
+        }
+    }
+    combos
+}
+
+/// The equity of a whole hero range, overall and for each of its hands.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeEquity {
+    // Chances over every deal, with each hero hand dealt as often as the ranges allow
+    pub equity: Equity,
+    // Every hero hand that was dealt, in range order
+    pub combos: Vec<ComboEquity>,
+}
+
+/// The hero's chances when holding one hand of a range.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComboEquity {
+    pub hand: [Card; 2],
+    // Share of the deals in which the hero holds this hand
+    pub frequency: f64,
+    pub equity: Equity,
+}
+
+/// The hero's Hold'em chances against one opponent holding a hand from each of `villains`.
+///
+/// Each opponent hand is dealt in proportion to its weight in the range, and hands that
+/// share a card with `hand`, `board`, the `dead` cards or another opponent are never dealt.
+/// A [`Card::UNKNOWN`] placeholder in `hand`, such as in `Ah ??`, can be any card left. As
+/// with [`crate::calculate_equity`], the rates are exact when every combination of opponent
+/// hands and boards can be counted within the `budget`, and simulated otherwise.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold 2 cards, if `board` holds more than 5 cards, if a card is
+/// given twice among `hand`, `board` and `dead`, if there are not from 1 to 22 villain
+/// ranges or the deck cannot complete every hand, or if a range has no hand left to play
+/// alongside the known cards and the other ranges.
+pub fn calculate_range_equity(
+    hand: &[Card],
+    board: &[Card],
+    dead: &[Card],
+    villains: &[Range],
+    budget: Budget,
+) -> Equity {
+    assert_eq!(
+        hand.len(),
+        2,
+        "Invalid hand length: expected 2 cards, found {}",
+        hand.len()
+    );
+    distinct_cards(hand.iter().chain(board).chain(dead));
+    let combos = hand_combos([hand[0], hand[1]]);
+    let hero = Range {
+        weights: vec![1.0; combos.len()],
+        combos,
+    };
+    // Hands blocked by the hero are dropped up front rather than dealt and thrown back
+    let hand_cards: CardSet = known_cards(hand).iter().collect();
+    let villains: Vec<Range> = villains
+        .iter()
+        .map(|range| range.without(hand_cards))
+        .collect();
+    calculate_range_vs_range(&hero, board, dead, &villains, budget).equity
+}
+
+/// Like [`calculate_range_equity`], for a hero holding any hand of the `hero` range.
+///
+/// Hero and opponent hands are dealt together by weight, never sharing a card, so the
+/// overall equity accounts for how the ranges block each other. The result also gives the
+/// equity of every hero hand on its own.
+///
+/// # Panics
+///
+/// Panics if `board` holds more than 5 cards, if a card is given twice among `board` and
+/// `dead`, if there are not from 1 to 22 villain ranges or the deck cannot complete every
+/// hand, or if a range has no hand left to play alongside the known cards and the other
+/// ranges.
+pub fn calculate_range_vs_range(
+    hero: &Range,
+    board: &[Card],
+    dead: &[Card],
+    villains: &[Range],
+    budget: Budget,
+) -> RangeEquity {
+    check_board_length(board);
+    let board = &known_cards(board)[..];
+    // Neither the board nor the dead cards can be in a hand or dealt to the board
+    let known = distinct_cards(board.iter().chain(dead));
+    let hero = hero.without(known);
+    let villains: Vec<Range> = villains.iter().map(|range| range.without(known)).collect();
+    let ranges: Vec<Range> = [hero.clone()].into_iter().chain(villains.clone()).collect();
+    check_player_count(ranges.len());
+    let needed = 2 * ranges.len() + 5 - board.len();
+    let left = (CardSet::full_deck() - known).len();
+    assert!(
+        needed <= left,
+        "Not enough cards to deal: {} needed, {} left",
+        needed,
+        left
+    );
+    assert!(
+        can_deal(&ranges, known),
+        "Range has no hand left to deal alongside the known cards and the other ranges"
+    );
+
+    // For each hero hand, its weighted scores and the number of deals they come from
+    let mut tallies = vec![([0.0; SEAT_SCORES], 0); hero.len()];
+
+    // Every combination of hands is counted with the boards it leaves, so this is an upper
+    // bound on the deals to count
+    let boards = deal_count(&Deal::new(2, &hero.combos[0], board, 1));
+    let combinations = villains.iter().fold(boards, |count, range| {
+        count.saturating_mul(range.len() as u128)
+    });
+    let exact = combinations.saturating_mul(hero.len() as u128) <= budget.iterations as u128;
+    if exact {
+        // Every deal counts as often as its hands are played together
+        for (index, &hand) in hero.combos.iter().enumerate() {
+            let (outcomes, count) = &mut tallies[index];
+            let mut used = known;
+            used.insert(hand[0]);
+            used.insert(hand[1]);
+            for_each_assignment(
+                &villains,
+                used,
+                hero.weights[index],
+                &mut Vec::new(),
+                &mut |opponents, weight, used| {
+                    let deal = Deal {
+                        deck: CardSet::full_deck() - used,
+                        ..Deal::new(2, &hand, board, 1)
+                    };
+                    *count += deal_count(&deal) as usize;
+                    let counts =
+                        enumerate_deals(deal, |hand, board, _| score(hand, board, opponents));
+                    for (total, count) in outcomes.iter_mut().zip(counts) {
+                        *total += weight * count as f64;
+                    }
+                },
+            );
+        }
+    } else {
+        let outcomes = match budget.seed {
+            Some(seed) => sample_ranges(&ranges, board, known, budget, &Seeded(seed)),
+            None => sample_ranges(&ranges, board, known, budget, &Entropy),
+        };
+        for (tally, counts) in tallies.iter_mut().zip(outcomes) {
+            *tally = (counts.map(|count| count as f64), counts[DEALT] as usize);
+        }
+    }
+
+    let total_count = tallies.iter().map(|(_, count)| count).sum();
+    let mut overall = [0.0; SEAT_SCORES];
+    for (outcomes, _) in &tallies {
+        for (total, outcome) in overall.iter_mut().zip(outcomes) {
+            *total += outcome;
+        }
+    }
+    RangeEquity {
+        equity: seat_equity(overall, total_count, exact),
+        combos: hero
+            .combos
+            .iter()
+            .zip(&tallies)
+            .filter(|(_, (outcomes, _))| outcomes[DEALT] > 0.0)
+            .map(|(&hand, &(outcomes, count))| ComboEquity {
+                hand,
+                frequency: outcomes[DEALT] / overall[DEALT],
+                equity: seat_equity(outcomes, count, exact),
+            })
+            .collect(),
+    }
+}
+
+// The hero's scores against every opponent hand on a full board
+fn score(hand: &[Card], board: &[Card], opponents: &[[Card; 2]]) -> SeatScores {
+    split_showdown(
+        evaluate_strength(hand, board),
+        opponents
+            .iter()
+            .map(|other_hand| evaluate_strength(other_hand, board)),
+    )
+}
+
+// Deals random hands from `ranges` by weight, the hero's first, and random boards until
+// the budget is spent, and gives the scores of each hero hand
+fn sample_ranges<R: RngSource>(
+    ranges: &[Range],
+    board: &[Card],
+    known: CardSet,
+    budget: Budget,
+    source: &R,
+) -> Vec<SeatTotals> {
+    let hero_len = ranges[0].len();
+    let samplers = samplers(ranges);
+    let deck: Vec<Card> = (CardSet::full_deck() - known).iter().collect();
+    let missing_board = 5 - board.len();
+    let start = Instant::now();
+    let mut outcomes = vec![[0; SEAT_SCORES]; hero_len];
+    let mut deals = 0;
+    while let Some(batch) = budget.next_batch(start, deals, seat_error(&combined(&outcomes), deals))
+    {
+        let counts = chunks(deals..deals + batch)
+            .map(|(chunk, size)| {
+                let mut rng = source.chunk_rng(chunk);
+                let mut deck = deck.clone();
+                let mut full_board = [board, &deck[..missing_board]].concat();
+                let mut picks = Vec::new();
+                let mut hands = Vec::new();
+                let mut totals = vec![[0; SEAT_SCORES]; hero_len];
+                for _ in 0..size {
+                    let used = deal_hands(ranges, &samplers, known, &mut picks, &mut rng);
+                    hands.clear();
+                    hands.extend(
+                        ranges
+                            .iter()
+                            .zip(picks.iter())
+                            .map(|(range, &pick)| range.combos[pick]),
+                    );
+                    // Enough cards are shuffled to the front to skip the ones in the hands
+                    let (front, _) =
+                        deck.partial_shuffle(&mut rng, missing_board + 2 * ranges.len());
+                    let runout = front.iter().filter(|&&card| !used.contains(card));
+                    for (slot, &card) in full_board[board.len()..].iter_mut().zip(runout) {
+                        *slot = card;
+                    }
+                    let counts = score(&hands[0], &full_board, &hands[1..]);
+                    totals[picks[0]] = add_totals(totals[picks[0]], counts);
+                }
+                totals
+            })
+            .reduce(
+                || vec![[0; SEAT_SCORES]; hero_len],
+                |mut totals, counts| {
+                    add_counts(&mut totals, &counts);
+                    totals
+                },
+            );
+        add_counts(&mut outcomes, &counts);
+        deals += batch;
+    }
+    outcomes
+}
+
+// Adds the scores of `counts` to those of the same hero hands in `totals`
+fn add_counts(totals: &mut [SeatTotals], counts: &[SeatTotals]) {
+    for (hand_totals, &hand_counts) in totals.iter_mut().zip(counts) {
+        *hand_totals = add_totals(*hand_totals, hand_counts);
+    }
+}
+
+// The scores of every hero hand together
+fn combined(outcomes: &[SeatTotals]) -> SeatTotals {
+    outcomes.iter().fold([0; SEAT_SCORES], |totals, &counts| {
+        add_totals(totals, counts)
+    })
+}
+
+// Whether a played hand can be picked from every range with no card shared
+fn can_deal(ranges: &[Range], used: CardSet) -> bool {
+    let Some((range, rest)) = ranges.split_first() else {
+        return true;
+    };
+    range
+        .combos
+        .iter()
+        .zip(&range.weights)
+        .any(|(combo, &weight)| {
+            let cards: CardSet = combo.iter().collect();
+            weight > 0.0 && (cards & used).is_empty() && can_deal(rest, used | cards)
+        })
+}
+
+// A sampler for each range, picking its hands in proportion to their weights
+fn samplers(ranges: &[Range]) -> Vec<WeightedIndex<f64>> {
+    ranges
+        .iter()
+        .map(|range| WeightedIndex::new(&range.weights).unwrap())
+        .collect()
+}
+
+// Picks the index of a hand from every range by weight with no card shared, starting over
+// whenever two hands collide so every such assignment keeps its odds, and returns every
+// card in use
+fn deal_hands(
+    ranges: &[Range],
+    samplers: &[WeightedIndex<f64>],
+    known: CardSet,
+    picks: &mut Vec<usize>,
+    rng: &mut impl Rng,
+) -> CardSet {
+    'deal: loop {
+        picks.clear();
+        let mut used = known;
+        for (range, sampler) in ranges.iter().zip(samplers) {
+            let pick = sampler.sample(rng);
+            let combo = range.combos[pick];
+            if !(used.insert(combo[0]) && used.insert(combo[1])) {
+                continue 'deal;
+            }
+            picks.push(pick);
+        }
+        return used;
+    }
+}
+
+// Calls `visit` with every way to pick a played hand from each range with no card shared,
+// the product of their weights, and every card in use
+fn for_each_assignment<F>(
+    villains: &[Range],
+    used: CardSet,
+    weight: f64,
+    opponents: &mut Vec<[Card; 2]>,
+    visit: &mut F,
+) where
+    F: FnMut(&[[Card; 2]], f64, CardSet),
+{
+    let Some((range, rest)) = villains.split_first() else {
+        return visit(opponents, weight, used);
+    };
+    for (&combo, &combo_weight) in range.combos.iter().zip(&range.weights) {
+        if combo_weight == 0.0 || used.contains(combo[0]) || used.contains(combo[1]) {
+            continue;
+        }
+        opponents.push(combo);
+        let mut used = used;
+        used.insert(combo[0]);
+        used.insert(combo[1]);
+        for_each_assignment(rest, used, weight * combo_weight, opponents, visit);
+        opponents.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn combos(input: &str) -> Vec<String> {
+        parse_range(input)
+            .combos()
+            .iter()
+            .map(|combo| format!("{}{}", combo[0], combo[1]))
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_classes() {
+        assert_eq!(parse_range("AKs").len(), 4);
+        assert_eq!(parse_range("AKo").len(), 12);
+        assert_eq!(parse_range("AK").len(), 16);
+        assert_eq!(parse_range("TT").len(), 6);
+        assert_eq!(parse_range("KA").combos(), parse_range("AK").combos());
+        assert_eq!(combos("JhTh"), vec!["JhTh"]);
+        assert_eq!(combos("Th Jh"), vec!["JhTh"]);
+    }
+
+    #[test]
+    fn test_parse_unknown_cards() {
+        assert_eq!(parse_range("Ah??").len(), 51);
+        assert_eq!(parse_range("?? Ah"), parse_range("Ah??"));
+        assert!(combos("Ah??").iter().all(|combo| combo.contains("Ah")));
+        assert_eq!(parse_range("????").len(), 1326);
+    }
+
+    #[test]
+    fn test_parse_plus_and_spans() {
+        assert_eq!(parse_range("TT+").len(), 5 * 6);
+        assert_eq!(parse_range("ATs+"), parse_range("ATs, AJs, AQs, AKs"));
+        assert_eq!(parse_range("A5s-A2s"), parse_range("A5s, A4s, A3s, A2s"));
+        assert_eq!(parse_range("66-99"), parse_range("99-66"));
+        assert_eq!(parse_range("99-66").len(), 4 * 6);
+    }
+
+    #[test]
+    fn test_parse_lists_count_hands_once() {
+        assert_eq!(parse_range("AKs, AK, AhKh").len(), 16);
+        assert_eq!(parse_range("QQ+, AKs, KQo").len(), 18 + 4 + 12);
+        assert_eq!(Range::any().len(), 1326);
+    }
+
+    #[test]
+    fn test_parse_weights() {
+        let input = "AKs:0.5, QQ : 0.75, AhKh, JJ";
+        let range = parse_range(input);
+        assert_eq!(range.len(), 4 + 6 + 6);
+        let weight = |combo: &str| {
+            let index = combos(input).iter().position(|other| other == combo);
+            range.weights()[index.unwrap()]
+        };
+        // Ace king of hearts keeps the frequency of its first entry
+        assert_eq!(weight("AhKh"), 0.5);
+        assert_eq!(weight("QhQd"), 0.75);
+        assert_eq!(weight("JcJs"), 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid range entry")]
+    fn test_weight_above_one() {
+        parse_range("AKs:1.5");
+    }
+
+    #[test]
+    fn test_weighted_exact_equity() {
+        // Aces against kings and queens on a dry turn, with queens played half as often
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("2c 7d 9h Js");
+        let kings = calculate_range_equity(
+            &hand,
+            &board,
+            &[],
+            &[parse_range("KK")],
+            Budget::iterations(10000),
+        );
+        let queens = calculate_range_equity(
+            &hand,
+            &board,
+            &[],
+            &[parse_range("QQ")],
+            Budget::iterations(10000),
+        );
+        let mixed = calculate_range_equity(
+            &hand,
+            &board,
+            &[],
+            &[parse_range("KK, QQ:0.5")],
+            Budget::iterations(10000),
+        );
+        assert!(mixed.exact);
+        let expected = (kings.win_rate * 2.0 + queens.win_rate) / 3.0;
+        assert!((mixed.win_rate - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_weighted_sampling() {
+        // 6 combos of nines against 6 combos of kings played a quarter of the time
+        let villains = [parse_range("KK:0.25, 99")];
+        let samplers = samplers(&villains);
+        let mut picks = Vec::new();
+        let mut rng = Seeded(1).chunk_rng(0);
+        let mut nines = 0;
+        for _ in 0..10000 {
+            deal_hands(&villains, &samplers, CardSet::EMPTY, &mut picks, &mut rng);
+            nines += (villains[0].combos()[picks[0]][0].value == 9) as usize;
+        }
+        assert!((nines as f64 / 10000.0 - 0.8).abs() < 0.03);
+    }
+
+    #[test]
+    #[should_panic(expected = "Range has no hand left")]
+    fn test_unplayed_range() {
+        calculate_range_equity(
+            &parse_cards("Ah Kd"),
+            &[],
+            &[],
+            &[parse_range("QQ:0, AhKh")],
+            Budget::DEFAULT,
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid range entry")]
+    fn test_suited_pair() {
+        parse_range("TTs");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid range entry")]
+    fn test_span_with_different_first_cards() {
+        parse_range("A5s-K2s");
+    }
+
+    #[test]
+    fn test_card_removal() {
+        let range = parse_range("AA, KK").without(parse_cards("Ah Ad Kc").iter().collect());
+        assert_eq!(range.len(), 1 + 3);
+    }
+
+    #[test]
+    fn test_exact_against_a_range() {
+        // Kings only beat aces with a king on the river
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("2c 7d 9h Js");
+        let equity = calculate_range_equity(
+            &hand,
+            &board,
+            &[],
+            &[parse_range("KK")],
+            Budget::iterations(10000),
+        );
+        assert!(equity.exact);
+        assert_eq!(equity.loss_rate, 2.0 / 44.0);
+        assert_eq!(equity.win_rate, 42.0 / 44.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: Ah")]
+    fn test_dead_card_in_hand() {
+        calculate_range_equity(
+            &parse_cards("Ah Kd"),
+            &[],
+            &parse_cards("Ah"),
+            &[parse_range("QQ+")],
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    fn test_dead_cards_against_a_range() {
+        // With the king of clubs out, each pair of kings has one king left among 43 rivers
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("2c 7d 9h Js");
+        let dead = parse_cards("Kc");
+        let equity = calculate_range_equity(
+            &hand,
+            &board,
+            &dead,
+            &[parse_range("KK")],
+            Budget::iterations(10000),
+        );
+        assert!(equity.exact);
+        assert_eq!(equity.loss_rate, 1.0 / 43.0);
+    }
+
+    #[test]
+    fn test_unknown_hero_card() {
+        // Holding the ace of hearts and any other card is the same as holding `Ah??`
+        let board = parse_cards("2c 7d 9h Js Ks");
+        let villains = [parse_range("QQ")];
+        let equity = calculate_range_equity(
+            &parse_cards("Ah ??"),
+            &board,
+            &[],
+            &villains,
+            Budget::iterations(10000),
+        );
+        let range = calculate_range_vs_range(
+            &parse_range("Ah??"),
+            &board,
+            &[],
+            &villains,
+            Budget::iterations(10000),
+        );
+        assert_eq!(equity, range.equity);
+    }
+
+    #[test]
+    fn test_simulated_against_ranges() {
+        let hand = parse_cards("Ah Ad");
+        let villains = [parse_range("KK"), parse_range("QQ")];
+        let equity = calculate_range_equity(&hand, &[], &[], &villains, Budget::iterations(10000));
+        assert!(!equity.exact);
+        assert!(equity.win_rate > 0.6 && equity.win_rate < 0.75);
+    }
+
+    #[test]
+    fn test_opponents_never_share_cards() {
+        // Only one pair of hands from these ranges holds no card twice
+        let villains = [parse_range("AhAd, AhAc"), parse_range("AhAd, AsAc")];
+        let expected = vec![
+            parse_range("AhAd").combos()[0],
+            parse_range("AsAc").combos()[0],
+        ];
+        let mut picks = Vec::new();
+        let mut rng = Seeded(2).chunk_rng(0);
+        for _ in 0..100 {
+            deal_hands(
+                &villains,
+                &samplers(&villains),
+                CardSet::EMPTY,
+                &mut picks,
+                &mut rng,
+            );
+            let hands: Vec<[Card; 2]> = villains
+                .iter()
+                .zip(&picks)
+                .map(|(range, &pick)| range.combos()[pick])
+                .collect();
+            assert_eq!(hands, expected);
+        }
+    }
+
+    #[test]
+    fn test_range_vs_range_exact() {
+        let board = parse_cards("2c 7d 9h Js");
+        let result = calculate_range_vs_range(
+            &parse_range("AA"),
+            &board,
+            &[],
+            &[parse_range("KK")],
+            Budget::iterations(100000),
+        );
+        assert!(result.equity.exact);
+        assert_eq!(result.combos.len(), 6);
+        for combo in &result.combos {
+            assert!((combo.frequency - 1.0 / 6.0).abs() < 1e-12);
+            assert!((combo.equity.win_rate - 42.0 / 44.0).abs() < 1e-12);
+        }
+        assert!((result.equity.win_rate - 42.0 / 44.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_range_vs_range_card_removal() {
+        // Aces of hearts and diamonds block one of the two villain hands, so they are dealt
+        // half as often as the kings
+        let board = parse_cards("2c 7d 9h Js 3s");
+        let hero = parse_range("AhAd, KhKd");
+        let villains = [parse_range("AhAc, AsAc")];
+        let result =
+            calculate_range_vs_range(&hero, &board, &[], &villains, Budget::iterations(100000));
+        assert!(result.equity.exact);
+        let [aces, kings] = [0, 1].map(|index| result.combos[index]);
+        assert_eq!(aces.hand, hero.combos()[0]);
+        assert!((aces.frequency - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(aces.equity.tie_rate, 1.0);
+        assert_eq!(kings.equity.loss_rate, 1.0);
+        assert!((result.equity.tie_rate - 1.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_seeded_range_vs_range() {
+        let budget = Budget::iterations(20000).with_seed(5);
+        let run = || {
+            calculate_range_vs_range(
+                &parse_range("AA, KQs"),
+                &[],
+                &[],
+                &[parse_range("TT+")],
+                budget,
+            )
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn test_range_vs_range_simulated() {
+        let result = calculate_range_vs_range(
+            &parse_range("AA, KK"),
+            &[],
+            &[],
+            &[parse_range("QQ")],
+            Budget::iterations(10000),
+        );
+        assert!(!result.equity.exact);
+        assert_eq!(result.combos.len(), 12);
+        assert!(result.equity.win_rate > 0.75 && result.equity.win_rate < 0.88);
+        let frequency: f64 = result.combos.iter().map(|combo| combo.frequency).sum();
+        assert!((frequency - 1.0).abs() < 1e-9);
+    }
+}
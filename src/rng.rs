@@ -0,0 +1,59 @@
+use rand::rngs::{StdRng, ThreadRng};
+use rand::{Rng, SeedableRng};
+
+// Where the simulator gets its random numbers, one generator for each chunk of deals.
+// Random deals are sampled in fixed size chunks numbered from 0, and every chunk draws only
+// from the generator made for its number, so a source that always gives the same generator
+// for the same number gives the same results on every run, however the chunks are spread
+// across threads. Tests inject their own sources to check which chunks are drawn.
+pub(crate) trait RngSource: Sync {
+    type Rng: Rng;
+
+    // The generator for the chunk numbered `chunk`
+    fn chunk_rng(&self, chunk: u64) -> Self::Rng;
+}
+
+// Fresh randomness from `rand::thread_rng`, different on every run
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Entropy;
+
+impl RngSource for Entropy {
+    type Rng = ThreadRng;
+
+    fn chunk_rng(&self, _chunk: u64) -> ThreadRng {
+        rand::thread_rng()
+    }
+}
+
+// Reproducible randomness, with a separate stream for every chunk derived from the seed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Seeded(pub(crate) u64);
+
+impl RngSource for Seeded {
+    type Rng = StdRng;
+
+    fn chunk_rng(&self, chunk: u64) -> StdRng {
+        // The seed and the chunk number together make the key of the chunk's stream
+        let mut key = [0; 32];
+        key[..8].copy_from_slice(&self.0.to_le_bytes());
+        key[8..16].copy_from_slice(&chunk.to_le_bytes());
+        StdRng::from_seed(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_streams() {
+        let first: Vec<u64> = (0..4).map(|_| Seeded(7).chunk_rng(3).gen()).collect();
+        assert!(first.iter().all(|&value| value == first[0]));
+        let mut rng = Seeded(7).chunk_rng(3);
+        let mut other_chunk = Seeded(7).chunk_rng(4);
+        let mut other_seed = Seeded(8).chunk_rng(3);
+        let value: u64 = rng.gen();
+        assert_ne!(value, other_chunk.gen::<u64>());
+        assert_ne!(value, other_seed.gen::<u64>());
+    }
+}
@@ -0,0 +1,171 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::card::Card;
+use crate::card_set::CardSet;
+use crate::evaluator::{order_best_hand, BestHand, HandRank};
+use crate::lookup::evaluate_set;
+
+/// A Short Deck hand, played with the 36 cards from six to ace.
+///
+/// A flush beats a full house, and `A-6-7-8-9` is the lowest straight, kept as a nine high
+/// [`HandRank::Straight`] or [`HandRank::StraightFlush`]. Otherwise hands compare like
+/// their [`HandRank`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShortDeckRank(HandRank);
+
+// A-6-7-8-9 as a rank mask
+const LOW_STRAIGHT: u16 = 0b1_0000_1111_0000;
+
+impl ShortDeckRank {
+    /// The hand category and tie-breaking values.
+    pub fn rank(self) -> HandRank {
+        self.0
+    }
+}
+
+// Position of each category in Short Deck order, from weakest to strongest
+fn category_order(rank: HandRank) -> u8 {
+    match rank {
+        HandRank::HighCard(..) => 0,
+        HandRank::OnePair(..) => 1,
+        HandRank::TwoPair(..) => 2,
+        HandRank::ThreeOfAKind(..) => 3,
+        HandRank::Straight(_) => 4,
+        HandRank::FullHouse(..) => 5,
+        HandRank::Flush(..) => 6,
+        HandRank::FourOfAKind(..) => 7,
+        HandRank::StraightFlush(_) => 8,
+        HandRank::RoyalFlush => 9,
+        HandRank::FiveOfAKind(_) => 10,
+    }
+}
+
+impl Ord for ShortDeckRank {
+    fn cmp(&self, other: &ShortDeckRank) -> Ordering {
+        (category_order(self.0), self.0).cmp(&(category_order(other.0), other.0))
+    }
+}
+
+impl PartialOrd for ShortDeckRank {
+    fn partial_cmp(&self, other: &ShortDeckRank) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for ShortDeckRank {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Evaluates the best Short Deck hand that can be made from `hand` and `board` together.
+///
+/// # Panics
+///
+/// Panics if fewer than 5 or more than 7 cards are given, or if any card is below a six.
+pub fn evaluate_short_deck(hand: &[Card], board: &[Card]) -> ShortDeckRank {
+    let cards: CardSet = hand.iter().chain(board).collect();
+    if let Some(card) = cards.iter().find(|card| card.value < 6) {
+        panic!("Invalid Short Deck card: {}", card);
+    }
+
+    // Seven cards cannot hold both a flush and a full house, so the standard evaluator never
+    // has to choose between them
+    let mut rank = evaluate_set(cards).rank();
+    // The standard evaluator only knows the A-2-3-4-5 wheel, so add the low straight here.
+    // Both take the place of the weakest straight, so the rest of the order still holds.
+    let suits = [0, 1, 2, 3].map(|suit| cards.suit_mask(suit));
+    if suits
+        .iter()
+        .any(|&suit| suit & LOW_STRAIGHT == LOW_STRAIGHT)
+    {
+        rank = rank.max(HandRank::StraightFlush(9));
+    } else if suits.iter().fold(0, |ranks, &suit| ranks | suit) & LOW_STRAIGHT == LOW_STRAIGHT {
+        rank = rank.max(HandRank::Straight(9));
+    }
+    ShortDeckRank(rank)
+}
+
+/// Like [`evaluate_short_deck`], but also returns the five cards that make the best hand.
+pub fn evaluate_best_short_deck_hand(hand: &[Card], board: &[Card]) -> BestHand {
+    let all_cards: Vec<Card> = hand.iter().chain(board).copied().collect();
+    assert!(
+        (5..=7).contains(&all_cards.len()),
+        "Invalid card count: expected 5 to 7 cards, found {}",
+        all_cards.len()
+    );
+
+    let mut best: Option<(ShortDeckRank, [Card; 5])> = None;
+    for subset in 0u32..(1 << all_cards.len()) {
+        if subset.count_ones() != 5 {
+            continue;
+        }
+        let mut cards = [all_cards[0]; 5];
+        for (slot, index) in (0..all_cards.len())
+            .filter(|i| subset & (1 << i) != 0)
+            .enumerate()
+        {
+            cards[slot] = all_cards[index];
+        }
+        let rank = evaluate_short_deck(&cards, &[]);
+        if best.is_none_or(|(best_rank, _)| rank > best_rank) {
+            best = Some((rank, cards));
+        }
+    }
+    let (rank, cards) = best.unwrap();
+    order_best_hand(rank.rank(), cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    fn rank(cards: &str) -> ShortDeckRank {
+        evaluate_short_deck(&parse_cards(cards), &[])
+    }
+
+    #[test]
+    fn test_flush_beats_full_house() {
+        let flush = rank("Ah Jh 9h 7h 6h");
+        let full_house = rank("Ks Kd Kc Qs Qd");
+        assert!(flush > full_house);
+        // The standard order is the other way around
+        assert!(flush.rank() < full_house.rank());
+        assert!(rank("6h 6d 6c 6s 7h") > flush);
+    }
+
+    #[test]
+    fn test_flush_beats_full_house_on_the_same_board() {
+        let board = parse_cards("Qh Jh 6h 6d 6c");
+        let flush = evaluate_short_deck(&parse_cards("Ah Kh"), &board);
+        let full_house = evaluate_short_deck(&parse_cards("Qd Qs"), &board);
+        assert_eq!(flush.rank(), HandRank::Flush(14, 13, 12, 11, 6));
+        assert_eq!(full_house.rank(), HandRank::FullHouse(12, 6));
+        assert!(flush > full_house);
+    }
+
+    #[test]
+    fn test_ace_six_straight() {
+        assert_eq!(rank("Ah 6d 7c 8s 9h").rank(), HandRank::Straight(9));
+        assert!(rank("Ah 6d 7c 8s 9h") < rank("6d 7c 8s 9h Th"));
+        assert!(rank("Ah 6d 7c 8s 9h") > rank("Ah Ad Ac 8s 9h"));
+        assert_eq!(rank("Ah 6h 7h 8h 9h").rank(), HandRank::StraightFlush(9));
+        assert!(rank("Ah 6h 7h 8h 9h") > rank("Ah Ad Ac As 9h"));
+    }
+
+    #[test]
+    fn test_best_short_deck_hand() {
+        let best =
+            evaluate_best_short_deck_hand(&parse_cards("Ah Kd"), &parse_cards("6c 7s 8h 9d Kh"));
+        assert_eq!(best.rank, HandRank::Straight(9));
+        assert_eq!(best.cards.to_vec(), parse_cards("9d 8h 7s 6c Ah"));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Short Deck card")]
+    fn test_low_cards_are_rejected() {
+        rank("Ah 2d 7c 8s 9h");
+    }
+}
@@ -0,0 +1,1815 @@
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+use rand::RngCore;
+use rayon::prelude::*;
+
+use crate::card::{check_known, known_cards, Card};
+use crate::card_set::CardSet;
+use crate::enumeration::{deal_count, enumerate_deals};
+use crate::game::Game;
+use crate::low::evaluate_low;
+use crate::rng::{Entropy, RngSource, Seeded};
+use crate::short_deck::evaluate_short_deck;
+use crate::wild::{evaluate_wild, Wilds};
+
+/// Estimates the hero's chances with `hand` on a partial `board` against
+/// `num_players - 1` random opponents.
+///
+/// Returns `(win_rate, tie_rate, loss_rate)`, each between 0 and 1.
+pub fn simulate_poker_hand(
+    hand: [Card; 2],
+    board: Vec<Card>,
+    num_players: usize,
+) -> (f64, f64, f64) {
+    simulate_game(Game::Holdem, &hand, &board, num_players)
+}
+
+/// Like [`simulate_poker_hand`], for any [`Game`].
+///
+/// `hand` must hold [`Game::hole_cards`] cards, and opponents are dealt the same number.
+///
+/// # Panics
+///
+/// Panics if `hand` has the wrong number of cards for `game`, if `board` holds more than 5
+/// cards, or if `num_players` is not from 2 to 23 or the deck cannot complete every hand.
+pub fn simulate_game(
+    game: Game,
+    hand: &[Card],
+    board: &[Card],
+    num_players: usize,
+) -> (f64, f64, f64) {
+    rates_of(calculate_equity(
+        game,
+        hand,
+        board,
+        &[],
+        num_players,
+        Budget::DEFAULT,
+    ))
+}
+
+/// The hero's chances, each between 0 and 1, and whether they are exact.
+///
+/// A tie is any deal in which the hero splits the pot, and [`Equity::pot_share`] counts
+/// the part of the pot each one returns.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Equity {
+    pub win_rate: f64,
+    pub tie_rate: f64,
+    pub loss_rate: f64,
+    // Average share of the pot won, where a pot split `n` ways gives a share of `1 / n`
+    pub pot_share: f64,
+    // Whether every possible deal was counted, rather than a Monte Carlo sample
+    pub exact: bool,
+    // Number of deals the rates were counted over
+    pub deals: usize,
+    // Rate of deals in which the pot is split `n` ways, at index `n`
+    split_rates: [f64; MAX_SEATS + 1],
+}
+
+impl Equity {
+    /// The rate of deals in which the hero splits the pot between `players` players,
+    /// the hero included. These add up to [`Equity::tie_rate`].
+    pub fn split_rate(&self, players: usize) -> f64 {
+        self.split_rates.get(players).copied().unwrap_or(0.0)
+    }
+
+    /// The hero's expected profit from a bet of 1 that each of the other `num_players - 1`
+    /// players calls, so the pot holds `num_players`.
+    pub fn bet_ev(&self, num_players: usize) -> f64 {
+        num_players as f64 * self.pot_share - 1.0
+    }
+
+    /// The 95% confidence interval of [`Equity::win_rate`] as `(low, high)`, which is the
+    /// rate itself when exact.
+    pub fn win_interval(&self) -> (f64, f64) {
+        self.interval(self.win_rate)
+    }
+
+    /// The 95% confidence interval of [`Equity::tie_rate`], like [`Equity::win_interval`].
+    pub fn tie_interval(&self) -> (f64, f64) {
+        self.interval(self.tie_rate)
+    }
+
+    fn interval(&self, rate: f64) -> (f64, f64) {
+        confidence_interval(rate, self.deals, self.exact)
+    }
+}
+
+// The 95% Wilson score interval of a rate measured over `deals` random deals, or the rate
+// itself when every deal was counted. Unlike `rate` plus or minus two standard errors, it
+// never shrinks to a single point when no deal, or every one, had the outcome.
+fn confidence_interval(rate: f64, deals: usize, exact: bool) -> (f64, f64) {
+    if exact {
+        return (rate, rate);
+    }
+    if deals == 0 {
+        return (0.0, 1.0);
+    }
+    let rate = rate.clamp(0.0, 1.0);
+    let (z, n) = (1.96, deals as f64);
+    let scale = 1.0 + z * z / n;
+    let center = (rate + z * z / (2.0 * n)) / scale;
+    let margin = z / scale * (rate * (1.0 - rate) / n + z * z / (4.0 * n * n)).sqrt();
+    ((center - margin).max(0.0), (center + margin).min(1.0))
+}
+
+/// How many random deals a simulation may sample, and when it can stop sooner.
+///
+/// Sampling stops after [`Budget::iterations`] deals, or as soon as every rate has reached
+/// the target standard error or the time limit has passed, whichever comes first. Deals
+/// are only counted one by one for exact rates when there are no more than `iterations`.
+///
+/// With a seed, the same budget always samples the same deals, on any number of threads,
+/// unless a time limit cuts sampling short.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Budget {
+    // Most deals to sample or to count
+    pub iterations: usize,
+    // Standard error every rate must be within before sampling stops early
+    pub target_error: Option<f64>,
+    // Time after which sampling stops early
+    pub time_limit: Option<Duration>,
+    // Seed of the random deals, or None for different deals on every run
+    pub seed: Option<u64>,
+}
+
+impl Budget {
+    /// The one million deals sampled when no budget is given.
+    pub const DEFAULT: Budget = Budget {
+        iterations: 1000000,
+        target_error: None,
+        time_limit: None,
+        seed: None,
+    };
+
+    /// A budget of `iterations` deals, with no early stop.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iterations` is 0.
+    pub fn iterations(iterations: usize) -> Budget {
+        assert!(iterations > 0, "Invalid iteration count: 0");
+        Budget {
+            iterations,
+            ..Budget::DEFAULT
+        }
+    }
+
+    /// The same budget, stopping once every rate has a standard error of at most `error`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `error` is not positive.
+    pub fn with_target_error(self, error: f64) -> Budget {
+        assert!(error > 0.0, "Invalid target error: {}", error);
+        Budget {
+            target_error: Some(error),
+            ..self
+        }
+    }
+
+    /// The same budget, stopping once sampling has taken `limit`.
+    pub fn with_time_limit(self, limit: Duration) -> Budget {
+        Budget {
+            time_limit: Some(limit),
+            ..self
+        }
+    }
+
+    /// The same budget, sampling the deals given by `seed`.
+    pub fn with_seed(self, seed: u64) -> Budget {
+        Budget {
+            seed: Some(seed),
+            ..self
+        }
+    }
+
+    // The number of deals to sample next, after `deals` of them whose rates have a standard
+    // error of `error` since `start`, or None once the budget is spent
+    pub(crate) fn next_batch(&self, start: Instant, deals: usize, error: f64) -> Option<usize> {
+        let precise = self.target_error.is_some_and(|target| error <= target);
+        // At least one batch is always dealt, so there are rates to give
+        let late = deals > 0
+            && self
+                .time_limit
+                .is_some_and(|limit| start.elapsed() >= limit);
+        if deals >= self.iterations || precise || late {
+            None
+        } else {
+            Some(BATCH_SIZE.min(self.iterations - deals))
+        }
+    }
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Budget::DEFAULT
+    }
+}
+
+// Random deals sampled between two checks of the budget
+const BATCH_SIZE: usize = 10000;
+
+// Random deals dealt from one generator of an `RngSource`, in order on a single thread;
+// batches are made of whole chunks so the chunks never depend on how the budget runs out
+pub(crate) const CHUNK_SIZE: usize = 1000;
+
+// The standard error of a rate measured over `deals` random deals
+pub(crate) fn standard_error(rate: f64, deals: usize) -> f64 {
+    if deals == 0 {
+        return f64::INFINITY;
+    }
+    let rate = rate.clamp(0.0, 1.0);
+    (rate * (1.0 - rate) / deals as f64).sqrt()
+}
+
+// The largest standard error among `rates` measured over `deals` random deals
+pub(crate) fn largest_error(rates: impl IntoIterator<Item = f64>, deals: usize) -> f64 {
+    rates
+        .into_iter()
+        .map(|rate| standard_error(rate, deals))
+        .fold(0.0, f64::max)
+}
+
+// The largest standard error among the rates of the `SeatScores` totals of every seat in
+// `totals` over `deals`: winning alone, each size of split, losing, and the pot share
+pub(crate) fn seat_error(totals: &[u128], deals: usize) -> f64 {
+    let rates = totals.chunks(SEAT_SCORES).flat_map(|seat| {
+        let lost = seat[DEALT] - seat[WON] - seat[SPLITS..].iter().sum::<u128>();
+        let counts = [lost, seat[WON]]
+            .into_iter()
+            .chain(seat[SPLITS..].iter().copied());
+        counts
+            .map(|count| count as f64)
+            .chain([seat[SHARE] as f64 / POT_UNITS as f64])
+            .map(|total| total / deals as f64)
+    });
+    largest_error(rates, deals)
+}
+
+/// Like [`simulate_game`], but also tells whether the result is exact.
+///
+/// When there are no more ways to complete the board and deal every opponent than the
+/// [`Budget::iterations`] of `budget`, such as on the turn against one opponent, each of
+/// them is counted once and the rates are exact. Otherwise they are estimated from random
+/// deals until the budget is spent.
+///
+/// The `dead` cards are out of play and never dealt, and any [`Card::UNKNOWN`] placeholder
+/// in `hand`, such as in `Ah ??`, is dealt at random like an opponent's cards.
+///
+/// # Panics
+///
+/// Panics if `hand` has the wrong number of cards for `game`, if `board` holds more than 5
+/// cards, if a card is given twice among `hand`, `board` and `dead`, or if `num_players`
+/// is not from 2 to 23 or the deck cannot complete every hand.
+pub fn calculate_equity(
+    game: Game,
+    hand: &[Card],
+    board: &[Card],
+    dead: &[Card],
+    num_players: usize,
+    budget: Budget,
+) -> Equity {
+    check_hand_length(game, hand);
+    check_board_length(board);
+    check_player_count(num_players);
+    let known = distinct_cards(hand.iter().chain(board).chain(dead));
+    let (hand, board) = (known_cards(hand), known_cards(board));
+    let tally = tally_deals(
+        Deal {
+            deck: CardSet::full_deck() - known,
+            ..Deal::new(game.hole_cards(), &hand, &board, num_players)
+        },
+        budget,
+        seat_error,
+        |hand, simulated_board, opponents| {
+            split_showdown(
+                game.evaluate(hand, simulated_board),
+                opponents
+                    .chunks(game.hole_cards())
+                    .map(|other_hand| game.evaluate(other_hand, simulated_board)),
+            )
+        },
+    );
+    tally_equity(tally)
+}
+
+/// Estimates the hero's chances in Razz, seven card stud played for the best ace-to-five
+/// low, against `num_players - 1` opponents.
+///
+/// `hand` holds the hero's known cards, up to all seven of them, and the rest of the
+/// hero's hand and every opponent hand are dealt at random.
+///
+/// Returns `(win_rate, tie_rate, loss_rate)`, each between 0 and 1.
+///
+/// # Panics
+///
+/// Panics if `hand` has more than 7 cards or holds an unknown card, or if `num_players` is
+/// not from 2 to 23 or the deck cannot complete every hand.
+pub fn simulate_razz(hand: &[Card], num_players: usize) -> (f64, f64, f64) {
+    rates_of(calculate_razz(hand, &[], num_players, Budget::DEFAULT))
+}
+
+/// Like [`simulate_razz`], dealing within `budget` as [`calculate_equity`] does and never
+/// dealing the `dead` cards.
+///
+/// # Panics
+///
+/// Panics if `hand` has more than 7 cards or holds an unknown card, if a card is given
+/// twice among `hand` and `dead`, or if `num_players` is not from 2 to 23 or the deck
+/// cannot complete every hand.
+pub fn calculate_razz(hand: &[Card], dead: &[Card], num_players: usize, budget: Budget) -> Equity {
+    assert!(
+        hand.len() <= 7,
+        "Invalid hand length: expected at most 7 cards, found {}",
+        hand.len()
+    );
+    check_known(hand, "Razz");
+    check_player_count(num_players);
+    let known = distinct_cards(hand.iter().chain(dead));
+    let deal = Deal {
+        deck: CardSet::full_deck() - known,
+        board_cards: 0,
+        ..Deal::new(7, hand, &[], num_players)
+    };
+    let tally = tally_deals(deal, budget, seat_error, |hand, _, opponents| {
+        split_showdown(evaluate_low(hand), opponents.chunks(7).map(evaluate_low))
+    });
+    tally_equity(tally)
+}
+
+/// Estimates the hero's chances with `hand` on a partial `board` in Short Deck Hold'em,
+/// dealt from the 36 cards from six to ace, against `num_players - 1` random opponents.
+///
+/// Any [`Card::UNKNOWN`] placeholder in `hand` is dealt at random, as in [`calculate_equity`].
+///
+/// Returns `(win_rate, tie_rate, loss_rate)`, each between 0 and 1.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold 2 cards, if `board` holds more than 5 cards, if any card
+/// is below a six, or if `num_players` is not from 2 to 23 or the deck cannot complete
+/// every hand.
+pub fn simulate_short_deck(hand: &[Card], board: &[Card], num_players: usize) -> (f64, f64, f64) {
+    rates_of(calculate_short_deck(
+        hand,
+        board,
+        &[],
+        num_players,
+        Budget::DEFAULT,
+    ))
+}
+
+/// Like [`simulate_short_deck`], dealing within `budget` as [`calculate_equity`] does and
+/// never dealing the `dead` cards.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold 2 cards, if `board` holds more than 5 cards, if any card
+/// is below a six, if a card is given twice among `hand`, `board` and `dead`, or if
+/// `num_players` is not from 2 to 23 or the deck cannot complete every hand.
+pub fn calculate_short_deck(
+    hand: &[Card],
+    board: &[Card],
+    dead: &[Card],
+    num_players: usize,
+    budget: Budget,
+) -> Equity {
+    check_hand_length(Game::Holdem, hand);
+    check_board_length(board);
+    check_player_count(num_players);
+    let known = distinct_cards(hand.iter().chain(board).chain(dead));
+    let (hand, board) = (known_cards(hand), known_cards(board));
+    let deal = Deal {
+        deck: CardSet::short_deck() - known,
+        ..Deal::new(2, &hand, &board, num_players)
+    };
+    let tally = tally_deals(
+        deal,
+        budget,
+        seat_error,
+        |hand, simulated_board, opponents| {
+            split_showdown(
+                evaluate_short_deck(hand, simulated_board),
+                opponents
+                    .chunks(2)
+                    .map(|other_hand| evaluate_short_deck(other_hand, simulated_board)),
+            )
+        },
+    );
+    tally_equity(tally)
+}
+
+/// Like [`simulate_poker_hand`], with the jokers of `wilds` shuffled into the deck and
+/// every hand ranked by [`evaluate_wild`].
+///
+/// Any [`Card::UNKNOWN`] placeholder in `hand` is dealt at random, as in [`calculate_equity`].
+///
+/// Returns `(win_rate, tie_rate, loss_rate)`, each between 0 and 1.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold 2 cards, if `board` holds more than 5 cards, or if
+/// `num_players` is not from 2 to 23 or the deck cannot complete every hand.
+pub fn simulate_wild(
+    hand: &[Card],
+    board: &[Card],
+    num_players: usize,
+    wilds: Wilds,
+) -> (f64, f64, f64) {
+    rates_of(calculate_wild(
+        hand,
+        board,
+        &[],
+        num_players,
+        wilds,
+        Budget::DEFAULT,
+    ))
+}
+
+/// Like [`simulate_wild`], dealing within `budget` as [`calculate_equity`] does and never
+/// dealing the `dead` cards, which may be jokers.
+///
+/// # Panics
+///
+/// Panics if `hand` does not hold 2 cards, if `board` holds more than 5 cards, if a card is
+/// given twice among `hand`, `board` and `dead`, or if `num_players` is not from 2 to 23
+/// or the deck cannot complete every hand.
+pub fn calculate_wild(
+    hand: &[Card],
+    board: &[Card],
+    dead: &[Card],
+    num_players: usize,
+    wilds: Wilds,
+    budget: Budget,
+) -> Equity {
+    check_hand_length(Game::Holdem, hand);
+    check_board_length(board);
+    check_player_count(num_players);
+    let jokers: CardSet = (0..wilds.jokers).map(Card::joker).collect();
+    let known = distinct_cards(hand.iter().chain(board).chain(dead));
+    let (hand, board) = (known_cards(hand), known_cards(board));
+    let deal = Deal {
+        deck: (CardSet::full_deck() | jokers) - known,
+        ..Deal::new(2, &hand, &board, num_players)
+    };
+    let tally = tally_deals(
+        deal,
+        budget,
+        seat_error,
+        |hand, simulated_board, opponents| {
+            split_showdown(
+                evaluate_wild(hand, simulated_board, wilds),
+                opponents
+                    .chunks(2)
+                    .map(|other_hand| evaluate_wild(other_hand, simulated_board, wilds)),
+            )
+        },
+    );
+    tally_equity(tally)
+}
+
+// Where each score of a seat goes in its `SeatScores`: one for every deal, one for every
+// pot won alone, the share of the pot in `POT_UNITS`, and after those at `SPLITS + n - 2`
+// one for every pot split `n` ways, from 2 to `MAX_SEATS`
+pub(crate) const DEALT: usize = 0;
+const WON: usize = 1;
+const SHARE: usize = 2;
+const SPLITS: usize = 3;
+pub(crate) const SEAT_SCORES: usize = SPLITS + MAX_SEATS - 1;
+
+// The scores of one seat for a single deal
+pub(crate) type SeatScores = [u64; SEAT_SCORES];
+
+// The scores of one seat summed over any number of deals, wide enough that the pot shares
+// of any budget cannot overflow
+pub(crate) type SeatTotals = [u128; SEAT_SCORES];
+
+// The scores of a seat for one deal, where it holds the best hand along with the other
+// `winners - 1` winners, or lost when `winners` is 0
+fn seat_scores(winners: usize) -> SeatScores {
+    let mut scores = [0; SEAT_SCORES];
+    scores[DEALT] = 1;
+    match winners {
+        0 => {}
+        1 => {
+            scores[WON] = 1;
+            scores[SHARE] = POT_UNITS;
+        }
+        _ => {
+            scores[SHARE] = POT_UNITS / winners as u64;
+            scores[SPLITS + winners - 2] = 1;
+        }
+    }
+    scores
+}
+
+// The hero's `SeatScores` for a single deal against every opponent hand, so split pots
+// count their share
+pub(crate) fn split_showdown<T: Ord>(player: T, others: impl Iterator<Item = T>) -> SeatScores {
+    let mut winners = 1;
+    for other in others {
+        match other.cmp(&player) {
+            Ordering::Greater => return seat_scores(0),
+            Ordering::Equal => winners += 1,
+            Ordering::Less => {}
+        }
+    }
+    seat_scores(winners)
+}
+
+// The equity of a seat from its `SeatScores` summed over deals, which may be weighted, and
+// which come from `count` distinct deals
+pub(crate) fn seat_equity(scores: [f64; SEAT_SCORES], count: usize, exact: bool) -> Equity {
+    let deals = scores[DEALT];
+    let splits = &scores[SPLITS..];
+    let ties: f64 = splits.iter().sum();
+    let mut split_rates = [0.0; MAX_SEATS + 1];
+    for (rate, split) in split_rates[2..].iter_mut().zip(splits) {
+        *rate = split / deals;
+    }
+    Equity {
+        win_rate: scores[WON] / deals,
+        tie_rate: ties / deals,
+        loss_rate: (deals - scores[WON] - ties) / deals,
+        pot_share: scores[SHARE] / POT_UNITS as f64 / deals,
+        exact,
+        deals: count,
+        split_rates,
+    }
+}
+
+// The equity of the hero's `SeatScores` summed over the deals of `tally`
+pub(crate) fn tally_equity(tally: Tally<SEAT_SCORES>) -> Equity {
+    seat_equity(
+        tally.totals.map(|total| total as f64),
+        tally.deals,
+        tally.exact,
+    )
+}
+
+// The win, tie and loss rates of `equity`
+pub(crate) fn rates_of(equity: Equity) -> (f64, f64, f64) {
+    (equity.win_rate, equity.tie_rate, equity.loss_rate)
+}
+
+// The known cards of `cards` together, where each may be given only once
+pub(crate) fn distinct_cards<'a>(cards: impl IntoIterator<Item = &'a Card>) -> CardSet {
+    let mut known = CardSet::EMPTY;
+    for &card in cards.into_iter().filter(|card| !card.is_unknown()) {
+        assert!(known.insert(card), "Duplicate card: {}", card);
+    }
+    known
+}
+
+// Panics unless there are from 2 to `MAX_SEATS` players
+pub(crate) fn check_player_count(num_players: usize) {
+    assert!(
+        (2..=MAX_SEATS).contains(&num_players),
+        "Invalid player count: expected 2 to {} players, found {}",
+        MAX_SEATS,
+        num_players
+    );
+}
+
+// Panics if `board` holds more than the five cards of a full board
+pub(crate) fn check_board_length(board: &[Card]) {
+    assert!(
+        board.len() <= 5,
+        "Invalid board length: expected at most 5 cards, found {}",
+        board.len()
+    );
+}
+
+fn check_hand_length(game: Game, hand: &[Card]) {
+    assert_eq!(
+        hand.len(),
+        game.hole_cards(),
+        "Invalid hand length: expected {} cards, found {}",
+        game.hole_cards(),
+        hand.len()
+    );
+}
+
+// The most players a deck can seat with two hole cards each and a full board
+pub(crate) const MAX_SEATS: usize = 23;
+
+/// Every player's chances when all of `hands` are known, such as when they are all-in,
+/// with one entry per hand in the same order.
+///
+/// A player wins when holding the best hand alone, and ties when sharing it. As with
+/// [`calculate_equity`], the rates are exact when every board can be counted within the
+/// `budget`, and simulated otherwise. The `dead` cards are never dealt to the board.
+///
+/// # Panics
+///
+/// Panics if there are fewer than 2 hands, if a hand has the wrong number of cards for
+/// `game` or holds an unknown card, if `board` holds more than 5 cards, or if a card is
+/// given twice.
+pub fn calculate_all_in(
+    game: Game,
+    hands: &[Vec<Card>],
+    board: &[Card],
+    dead: &[Card],
+    budget: Budget,
+) -> Vec<Equity> {
+    assert!(
+        (2..=MAX_SEATS).contains(&hands.len()),
+        "Invalid player count: expected 2 to {} hands, found {}",
+        MAX_SEATS,
+        hands.len()
+    );
+    check_board_length(board);
+    let board = &known_cards(board)[..];
+    // The board and dead cards, then every hole card, may each be given only once
+    let mut known = distinct_cards(board.iter().chain(dead));
+    for hand in hands {
+        check_hand_length(game, hand);
+        assert!(
+            hand.iter().all(|card| !card.is_unknown()),
+            "Every all-in hand must be known"
+        );
+        for &card in hand {
+            assert!(known.insert(card), "Duplicate card: {}", card);
+        }
+    }
+
+    // Only the board is dealt, from the cards nobody holds that are still in play
+    let deal = Deal {
+        deck: CardSet::full_deck() - known,
+        ..Deal::new(0, &[], board, 1)
+    };
+    // The `SeatScores` of every seat one after another
+    let tally = tally_deals(deal, budget, seat_error, |_, simulated_board, _| {
+        let ranks: Vec<_> = hands
+            .iter()
+            .map(|hand| game.evaluate(hand, simulated_board))
+            .collect();
+        let best = ranks.iter().max().unwrap();
+        let winners = ranks.iter().filter(|&rank| rank == best).count();
+        let mut counts = [0; MAX_SEATS * SEAT_SCORES];
+        for (scores, rank) in counts.chunks_mut(SEAT_SCORES).zip(&ranks) {
+            scores.copy_from_slice(&seat_scores(if rank == best { winners } else { 0 }));
+        }
+        counts
+    });
+
+    tally
+        .totals
+        .chunks(SEAT_SCORES)
+        .take(hands.len())
+        .map(|scores| {
+            let scores: SeatTotals = scores.try_into().unwrap();
+            seat_equity(scores.map(|total| total as f64), tally.deals, tally.exact)
+        })
+        .collect()
+}
+
+/// Outcome rates of a split pot hand from the hero's point of view, each between 0 and 1.
+///
+/// The first five rates cover every deal exactly once, so they add up to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HiLoResult {
+    // Won the whole pot alone, either both halves or the high half when no low qualified
+    pub scoop: f64,
+    // Won the high half alone and nothing of the low half
+    pub high_only: f64,
+    // Won the low half alone and nothing of the high half
+    pub low_only: f64,
+    // Won part of the pot while sharing at least one half, such as a quarter of it
+    pub quartered: f64,
+    // Won nothing
+    pub lose: f64,
+    // Average share of the pot won
+    pub equity: f64,
+    // Whether every possible deal was counted, rather than a Monte Carlo sample
+    pub exact: bool,
+    // Number of deals the rates were counted over
+    pub deals: usize,
+}
+
+impl HiLoResult {
+    /// The 95% confidence interval of any of the outcome rates as `(low, high)`, like
+    /// [`Equity::win_interval`].
+    pub fn interval(&self, rate: f64) -> (f64, f64) {
+        confidence_interval(rate, self.deals, self.exact)
+    }
+}
+
+// A pot in units that split evenly into two halves and then between any number of players
+// up to 23, the most a deck can seat
+const POT_UNITS: u64 = 2 * 5_354_228_880;
+
+/// Estimates the hero's split pot results with `hand` on a partial `board` against
+/// `num_players - 1` random opponents, where the best high hand and the best
+/// eight-or-better low each win half of the pot.
+///
+/// When nobody makes a qualifying low, the best high hand wins the whole pot. Any
+/// [`Card::UNKNOWN`] placeholder in `hand` is dealt at random, as in [`calculate_equity`].
+///
+/// # Panics
+///
+/// Panics if `hand` has the wrong number of cards for `game`, if `board` holds more than 5
+/// cards, or if `num_players` is not from 2 to 23 or the deck cannot complete every hand.
+pub fn simulate_hi_lo(game: Game, hand: &[Card], board: &[Card], num_players: usize) -> HiLoResult {
+    calculate_hi_lo(game, hand, board, &[], num_players, Budget::DEFAULT)
+}
+
+/// Like [`simulate_hi_lo`], dealing within `budget` as [`calculate_equity`] does and never
+/// dealing the `dead` cards.
+///
+/// # Panics
+///
+/// Panics if `hand` has the wrong number of cards for `game`, if `board` holds more than 5
+/// cards, if a card is given twice among `hand`, `board` and `dead`, or if `num_players`
+/// is not from 2 to 23 or the deck cannot complete every hand.
+pub fn calculate_hi_lo(
+    game: Game,
+    hand: &[Card],
+    board: &[Card],
+    dead: &[Card],
+    num_players: usize,
+    budget: Budget,
+) -> HiLoResult {
+    check_hand_length(game, hand);
+    check_board_length(board);
+    check_player_count(num_players);
+    let known = distinct_cards(hand.iter().chain(board).chain(dead));
+    let (hand, board) = (known_cards(hand), known_cards(board));
+    let tally = tally_deals(
+        Deal {
+            deck: CardSet::full_deck() - known,
+            ..Deal::new(game.hole_cards(), &hand, &board, num_players)
+        },
+        budget,
+        hi_lo_error,
+        |hand, simulated_board, opponents| {
+            let player_high = game.evaluate(hand, simulated_board);
+            let player_low = game.evaluate_low(hand, simulated_board);
+
+            let mut high_lost = false;
+            let mut high_winners = 1;
+            let mut best_low = player_low;
+            let mut low_winners = 1;
+
+            for other_hand in opponents.chunks(game.hole_cards()) {
+                let other_high = game.evaluate(other_hand, simulated_board);
+                if other_high > player_high {
+                    high_lost = true;
+                } else if other_high == player_high {
+                    high_winners += 1;
+                }
+
+                let other_low = game.evaluate_low(other_hand, simulated_board);
+                if other_low > best_low {
+                    best_low = other_low;
+                    low_winners = 1;
+                } else if other_low == best_low {
+                    low_winners += 1;
+                }
+            }
+
+            let high_pot = if best_low.is_some() {
+                POT_UNITS / 2
+            } else {
+                POT_UNITS
+            };
+            let high_share = if high_lost {
+                0
+            } else {
+                high_pot / high_winners
+            };
+            let low_share = if best_low.is_some() && player_low == best_low {
+                POT_UNITS / 2 / low_winners
+            } else {
+                0
+            };
+            let share = high_share + low_share;
+
+            // Any half shared with another player is a split, whatever its share
+            let high_alone = !high_lost && high_winners == 1;
+            let low_alone = low_share > 0 && low_winners == 1;
+            let outcome = if share == 0 {
+                [0, 0, 0, 0, 1]
+            } else if high_alone && (best_low.is_none() || low_alone) {
+                [1, 0, 0, 0, 0]
+            } else if high_alone && low_share == 0 {
+                [0, 1, 0, 0, 0]
+            } else if low_alone && high_share == 0 {
+                [0, 0, 1, 0, 0]
+            } else {
+                [0, 0, 0, 1, 0]
+            };
+            let [a, b, c, d, e] = outcome;
+            [a, b, c, d, e, share]
+        },
+    );
+
+    let [scoop, high_only, low_only, quartered, lose, share] = tally.totals;
+    let rate = |count: u128| count as f64 / tally.deals as f64;
+    HiLoResult {
+        scoop: rate(scoop),
+        high_only: rate(high_only),
+        low_only: rate(low_only),
+        quartered: rate(quartered),
+        lose: rate(lose),
+        equity: share as f64 / POT_UNITS as f64 / tally.deals as f64,
+        exact: tally.exact,
+        deals: tally.deals,
+    }
+}
+
+// The largest standard error among the rates of the totals `calculate_hi_lo` scores over
+// `deals`: the five outcomes, and the pot share
+fn hi_lo_error(totals: &[u128], deals: usize) -> f64 {
+    let (outcomes, share) = totals.split_at(5);
+    let rates = outcomes
+        .iter()
+        .map(|&count| count as f64)
+        .chain([share[0] as f64 / POT_UNITS as f64])
+        .map(|total| total / deals as f64);
+    largest_error(rates, deals)
+}
+
+// The cards dealt in each simulated hand: the hero's known cards and the board completed
+// from `deck` to `hole_cards` and `board_cards`, and `hole_cards` for every opponent
+#[derive(Clone, Copy)]
+pub(crate) struct Deal<'a> {
+    pub(crate) deck: CardSet,
+    pub(crate) hand: &'a [Card],
+    pub(crate) hole_cards: usize,
+    pub(crate) board: &'a [Card],
+    pub(crate) board_cards: usize,
+    pub(crate) num_players: usize,
+}
+
+impl<'a> Deal<'a> {
+    // A deal from the full deck with a five card board
+    pub(crate) fn new(
+        hole_cards: usize,
+        hand: &'a [Card],
+        board: &'a [Card],
+        num_players: usize,
+    ) -> Self {
+        assert!(
+            hand.len() <= hole_cards,
+            "Invalid hand length: expected at most {} cards, found {}",
+            hole_cards,
+            hand.len()
+        );
+        check_board_length(board);
+        Deal {
+            deck: CardSet::full_deck(),
+            hand,
+            hole_cards,
+            board,
+            board_cards: 5,
+            num_players,
+        }
+    }
+
+    // The number of cards still to deal to the hero's hand and to the board
+    pub(crate) fn missing_cards(&self) -> (usize, usize) {
+        let missing = |total: usize, known: usize, what: &str| {
+            total.checked_sub(known).unwrap_or_else(|| {
+                panic!(
+                    "Invalid {} length: expected at most {} cards, found {}",
+                    what, total, known
+                )
+            })
+        };
+        (
+            missing(self.hole_cards, self.hand.len(), "hand"),
+            missing(self.board_cards, self.board.len(), "board"),
+        )
+    }
+
+    // The number of cards dealt each time: the rest of the hero's hand and of the board,
+    // and every opponent hand
+    pub(crate) fn cards_needed(&self) -> usize {
+        let (missing_hand, missing_board) = self.missing_cards();
+        missing_hand + missing_board + self.hole_cards * (self.num_players - 1)
+    }
+
+    // Panics if the deck has too few cards left to complete every hand and the board
+    pub(crate) fn check_deck(&self) {
+        let (needed, left) = (self.cards_needed(), self.unknown_cards().len());
+        assert!(
+            needed <= left,
+            "Not enough cards to deal: {} needed, {} left",
+            needed,
+            left
+        );
+    }
+
+    // The cards of the deck that are still to be dealt
+    pub(crate) fn unknown_cards(&self) -> Vec<Card> {
+        let known: CardSet = self.hand.iter().chain(self.board).collect();
+        (self.deck - known).iter().collect()
+    }
+}
+
+// The summed scores of a run of deals, and how many deals they cover
+pub(crate) struct Tally<const N: usize> {
+    pub(crate) totals: [u128; N],
+    pub(crate) deals: usize,
+    // Whether every possible deal was scored once, rather than a random sample
+    pub(crate) exact: bool,
+}
+
+// Scores every possible completion of `deal` when there are no more of them than
+// `budget.iterations`, and random ones in batches until the budget is spent otherwise,
+// where `error` is the largest standard error of the rates that summed scores give
+pub(crate) fn tally_deals<const N: usize, F>(
+    deal: Deal,
+    budget: Budget,
+    error: fn(&[u128], usize) -> f64,
+    score: F,
+) -> Tally<N>
+where
+    F: Fn(&[Card], &[Card], &[Card]) -> [u64; N] + Sync,
+{
+    match budget.seed {
+        Some(seed) => tally_deals_from(deal, budget, &Seeded(seed), error, score),
+        None => tally_deals_from(deal, budget, &Entropy, error, score),
+    }
+}
+
+// Like `tally_deals`, drawing random deals from `source`
+pub(crate) fn tally_deals_from<const N: usize, F, R>(
+    deal: Deal,
+    budget: Budget,
+    source: &R,
+    error: fn(&[u128], usize) -> f64,
+    score: F,
+) -> Tally<N>
+where
+    F: Fn(&[Card], &[Card], &[Card]) -> [u64; N] + Sync,
+    R: RngSource,
+{
+    deal.check_deck();
+    let count = deal_count(&deal);
+    if count <= budget.iterations as u128 {
+        return Tally {
+            totals: enumerate_deals(deal, score),
+            deals: count as usize,
+            exact: true,
+        };
+    }
+
+    sample_batches(budget, error, |deals| {
+        run_deals(deal, deals, source, &score)
+    })
+}
+
+// Samples random deals in batches until `budget` is spent, where `run` deals the ones
+// numbered by a range and returns their summed scores, and `error` measures those totals
+// against the budget's target error
+fn sample_batches<const N: usize>(
+    budget: Budget,
+    error: fn(&[u128], usize) -> f64,
+    run: impl Fn(Range<usize>) -> [u128; N],
+) -> Tally<N> {
+    let start = Instant::now();
+    let mut totals = [0; N];
+    let mut deals = 0;
+    while let Some(batch) = budget.next_batch(start, deals, error(&totals, deals)) {
+        totals = add_totals(totals, run(deals..deals + batch));
+        deals += batch;
+    }
+    Tally {
+        totals,
+        deals,
+        exact: false,
+    }
+}
+
+// Scores random deals until `budget` is spent, for games that need random numbers while a
+// hand is played, such as draws. `score` plays one deal with the generator of its chunk and
+// the scratch state that `init` makes for every chunk, and `error` measures the totals as
+// in `tally_deals`.
+pub(crate) fn sample_deals<const N: usize, S>(
+    budget: Budget,
+    error: fn(&[u128], usize) -> f64,
+    init: impl Fn() -> S + Sync,
+    score: impl Fn(&mut S, &mut dyn RngCore) -> [u64; N] + Sync,
+) -> Tally<N> {
+    match budget.seed {
+        Some(seed) => sample_deals_from(budget, &Seeded(seed), error, init, score),
+        None => sample_deals_from(budget, &Entropy, error, init, score),
+    }
+}
+
+// Like `sample_deals`, drawing random numbers from `source`
+fn sample_deals_from<const N: usize, S, R: RngSource>(
+    budget: Budget,
+    source: &R,
+    error: fn(&[u128], usize) -> f64,
+    init: impl Fn() -> S + Sync,
+    score: impl Fn(&mut S, &mut dyn RngCore) -> [u64; N] + Sync,
+) -> Tally<N> {
+    sample_batches(budget, error, |deals| {
+        chunks(deals)
+            .map(|(chunk, size)| {
+                let mut rng = source.chunk_rng(chunk);
+                let mut state = init();
+                let mut totals = [0; N];
+                for _ in 0..size {
+                    totals = add_totals(totals, score(&mut state, &mut rng));
+                }
+                totals
+            })
+            .reduce(|| [0; N], add_totals)
+    })
+}
+
+// Adds the scores of one deal, or the totals of other deals, to `totals`
+pub(crate) fn add_totals<const N: usize, T: Into<u128>>(
+    mut totals: [u128; N],
+    counts: [T; N],
+) -> [u128; N] {
+    for (total, count) in totals.iter_mut().zip(counts) {
+        *total += count.into();
+    }
+    totals
+}
+
+// The chunks of the deals numbered `deals`, as the number of each chunk and the deals in it
+pub(crate) fn chunks(deals: Range<usize>) -> impl IndexedParallelIterator<Item = (u64, usize)> {
+    debug_assert!(deals.start.is_multiple_of(CHUNK_SIZE));
+    let first = deals.start / CHUNK_SIZE;
+    (first..deals.end.div_ceil(CHUNK_SIZE))
+        .into_par_iter()
+        .map(move |chunk| {
+            let size = CHUNK_SIZE.min(deals.end - chunk * CHUNK_SIZE);
+            (chunk as u64, size)
+        })
+}
+
+// Deals the random completions of `deal` numbered `deals`, and adds up what `score` returns
+// for each one. `score` gets the hero's full hand, the full board and the hole cards of all
+// opponents one after another.
+pub(crate) fn run_deals<const N: usize, F, R>(
+    deal: Deal,
+    deals: Range<usize>,
+    source: &R,
+    score: F,
+) -> [u128; N]
+where
+    F: Fn(&[Card], &[Card], &[Card]) -> [u64; N] + Sync,
+    R: RngSource,
+{
+    let deck = deal.unknown_cards();
+    let (missing_hand, missing_board) = deal.missing_cards();
+    let Deal { hand, board, .. } = deal;
+    let cards_needed = deal.cards_needed();
+
+    chunks(deals)
+        .map(|(chunk, size)| {
+            let mut rng = source.chunk_rng(chunk);
+            let mut deck = deck.clone();
+            // The known cards stay in place and only the rest is dealt over each time
+            let mut full_hand = [hand, &deck[..missing_hand]].concat();
+            let mut full_board = [board, &deck[..missing_board]].concat();
+            let mut totals = [0; N];
+            for _ in 0..size {
+                // Only the cards that are dealt need to be shuffled into place
+                let (dealt, _) = deck.partial_shuffle(&mut rng, cards_needed);
+                let (hand_cards, dealt) = dealt.split_at(missing_hand);
+                let (runout, opponents) = dealt.split_at(missing_board);
+                full_hand[hand.len()..].copy_from_slice(hand_cards);
+                full_board[board.len()..].copy_from_slice(runout);
+                totals = add_totals(totals, score(&full_hand, &full_board, opponents));
+            }
+            totals
+        })
+        .reduce(|| [0; N], add_totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use crate::evaluator::evaluate_hand;
+
+    #[test]
+    fn test_board_plays_for_everyone() {
+        let hand = parse_cards("2c 3d");
+        let board = parse_cards("Ah Kh Qh Jh Th");
+        let (win_rate, tie_rate, loss_rate) = simulate_poker_hand([hand[0], hand[1]], board, 3);
+        assert_eq!((win_rate, tie_rate, loss_rate), (0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_three_way_split_returns_a_third() {
+        let hand = parse_cards("2c 3d");
+        let board = parse_cards("Ah Kh Qh Jh Th");
+        let budget = Budget::iterations(10000);
+        let equity = calculate_equity(Game::Holdem, &hand, &board, &[], 3, budget);
+        assert_eq!(equity.tie_rate, 1.0);
+        assert_eq!(equity.split_rate(3), 1.0);
+        assert_eq!(equity.split_rate(2), 0.0);
+        assert!((equity.pot_share - 1.0 / 3.0).abs() < 1e-12);
+        assert!(equity.bet_ev(3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_pot_share_counts_split_size() {
+        // Aces full lose only to Kc Ks, and split with nobody
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("As Kh Kd 7c 2s");
+        let equity = calculate_equity(Game::Holdem, &hand, &board, &[], 2, Budget::DEFAULT);
+        assert_eq!(equity.pot_share, equity.win_rate);
+
+        // The board straight is only beaten by an ace, so the hero either loses or splits
+        // three ways with two opponents who play the board too
+        let hand = parse_cards("2c 3d");
+        let board = parse_cards("9h Tc Jd Qs Kh");
+        let equity = calculate_equity(Game::Holdem, &hand, &board, &[], 3, Budget::DEFAULT);
+        assert_eq!(equity.split_rate(2), 0.0);
+        assert_eq!(equity.split_rate(3), equity.tie_rate);
+        assert!((equity.pot_share - equity.tie_rate / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_split_scores_stay_apart() {
+        // Every split size has its own score, after the fixed ones
+        for winners in 2..=MAX_SEATS {
+            let scores = seat_scores(winners);
+            assert_eq!(scores[WON], 0);
+            assert_eq!(scores[SHARE], POT_UNITS / winners as u64);
+            assert_eq!(scores.iter().filter(|&&score| score == 1).count(), 2);
+            let equity = seat_equity(scores.map(|score| score as f64), 1, false);
+            assert_eq!(equity.split_rate(winners), 1.0);
+            assert_eq!(equity.tie_rate, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_totals_do_not_overflow() {
+        // Two billion pots won outgrow a u64
+        let totals = add_totals([u64::MAX as u128], [POT_UNITS]);
+        assert_eq!(totals[0], u64::MAX as u128 + POT_UNITS as u128);
+    }
+
+    #[test]
+    fn test_pot_share_is_measured_in_pots() {
+        // A pot won, one split two ways and one lost: each outcome has a rate of a third, but
+        // the share of half the pots varies more than any of them
+        let totals = [1, 2, 0]
+            .map(seat_scores)
+            .into_iter()
+            .fold([0; SEAT_SCORES], add_totals);
+        assert_eq!(totals[SHARE] as f64 / POT_UNITS as f64 / 3.0, 0.5);
+        assert_eq!(seat_error(&totals, 3), standard_error(0.5, 3));
+    }
+
+    #[test]
+    fn test_nuts_always_win() {
+        let hand = parse_cards("Ah Kh");
+        let board = parse_cards("Qh Jh Th");
+        let equity = calculate_equity(
+            Game::Holdem,
+            &hand,
+            &board,
+            &[],
+            4,
+            Budget::iterations(10000),
+        );
+        assert_eq!(
+            (equity.win_rate, equity.tie_rate, equity.loss_rate),
+            (1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_exact_on_the_river() {
+        let hand = parse_cards("Ah 2c");
+        let board = parse_cards("Kh Kd 7c 7s 3d");
+        let equity = calculate_equity(
+            Game::Holdem,
+            &hand,
+            &board,
+            &[],
+            2,
+            Budget::iterations(10000),
+        );
+        assert!(equity.exact);
+
+        // Every opponent hand, counted one by one
+        let known: CardSet = hand.iter().chain(&board).collect();
+        let deck: Vec<Card> = (CardSet::full_deck() - known).iter().collect();
+        let player = evaluate_hand(&hand, &board);
+        let mut outcomes = [0; 3];
+        for (i, &first) in deck.iter().enumerate() {
+            for &second in &deck[i + 1..] {
+                let outcome = match evaluate_hand(&[first, second], &board).cmp(&player) {
+                    Ordering::Less => 0,
+                    Ordering::Equal => 1,
+                    Ordering::Greater => 2,
+                };
+                outcomes[outcome] += 1;
+            }
+        }
+        let [wins, ties, losses] = outcomes.map(|count| count as f64 / 990.0);
+        assert_eq!(equity.win_rate, wins);
+        assert_eq!(equity.tie_rate, ties);
+        assert_eq!(equity.loss_rate, losses);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: Ah")]
+    fn test_dead_card_in_hand() {
+        calculate_equity(
+            Game::Holdem,
+            &parse_cards("Ah Ad"),
+            &parse_cards("As 7c 2d"),
+            &parse_cards("Ah Kc"),
+            2,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    fn test_dead_cards_are_never_dealt() {
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("As Kh Kd 7c 2s");
+        // Only Kc Ks beats aces full, out of 990 opponent hands
+        let equity = calculate_equity(
+            Game::Holdem,
+            &hand,
+            &board,
+            &[],
+            2,
+            Budget::iterations(10000),
+        );
+        assert_eq!(equity.loss_rate, 1.0 / 990.0);
+        let equity = calculate_equity(
+            Game::Holdem,
+            &hand,
+            &board,
+            &parse_cards("Ks"),
+            2,
+            Budget::iterations(10000),
+        );
+        assert!(equity.exact);
+        assert_eq!(equity.loss_rate, 0.0);
+    }
+
+    #[test]
+    fn test_unknown_hole_card() {
+        let board = parse_cards("Kh Kd 7c 7s 3d");
+        let equity = calculate_equity(
+            Game::Holdem,
+            &parse_cards("Ah ??"),
+            &board,
+            &[],
+            2,
+            Budget::iterations(50000),
+        );
+        assert!(equity.exact);
+
+        // The same as averaging over every card the placeholder can be
+        let known: CardSet = parse_cards("Ah").iter().chain(&board).collect();
+        let seconds: Vec<Card> = (CardSet::full_deck() - known).iter().collect();
+        let average = seconds
+            .iter()
+            .map(|&second| {
+                let hand = [parse_cards("Ah")[0], second];
+                calculate_equity(
+                    Game::Holdem,
+                    &hand,
+                    &board,
+                    &[],
+                    2,
+                    Budget::iterations(10000),
+                )
+                .win_rate
+            })
+            .sum::<f64>()
+            / seconds.len() as f64;
+        assert!((equity.win_rate - average).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_estimated_before_the_flop() {
+        let hand = parse_cards("Ah Ad");
+        let equity = calculate_equity(Game::Holdem, &hand, &[], &[], 2, Budget::iterations(10000));
+        assert!(!equity.exact);
+        assert!(equity.win_rate > 0.8);
+    }
+
+    #[test]
+    fn test_target_error_stops_early() {
+        let hand = parse_cards("Ah Ad");
+        let budget = Budget::iterations(1000000).with_target_error(0.01);
+        let equity = calculate_equity(Game::Holdem, &hand, &[], &[], 2, budget);
+        // Aces win about 85% of the time, so 10,000 deals give a standard error near 0.0036
+        assert_eq!(equity.deals, 10000);
+        let (low, high) = equity.win_interval();
+        assert!(low < equity.win_rate && equity.win_rate < high);
+        // Away from 0 and 1, the interval is close to two standard errors either way
+        assert!((high - low - 2.0 * 1.96 * standard_error(equity.win_rate, 10000)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_time_limit_stops_early() {
+        let hand = parse_cards("Ah Ad");
+        let budget = Budget::iterations(1000000).with_time_limit(Duration::ZERO);
+        let equity = calculate_equity(Game::Holdem, &hand, &[], &[], 2, budget);
+        // The budget is checked after each batch, so one batch is always dealt
+        assert_eq!(equity.deals, 10000);
+    }
+
+    #[test]
+    fn test_seed_is_independent_of_threads() {
+        let hand = parse_cards("Ah Kd");
+        let budget = Budget::iterations(25000).with_seed(42);
+        let run = |threads| {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            pool.install(|| calculate_equity(Game::Holdem, &hand, &[], &[], 3, budget))
+        };
+        let equity = run(1);
+        assert_eq!(equity, run(4));
+        assert_eq!(equity.deals, 25000);
+        let other = calculate_equity(Game::Holdem, &hand, &[], &[], 3, budget.with_seed(43));
+        assert_ne!(equity, other);
+    }
+
+    // Seeded streams that remember which chunks were asked for
+    struct Recording(std::sync::Mutex<Vec<u64>>);
+
+    impl RngSource for Recording {
+        type Rng = rand::rngs::StdRng;
+
+        fn chunk_rng(&self, chunk: u64) -> Self::Rng {
+            self.0.lock().unwrap().push(chunk);
+            Seeded(0).chunk_rng(chunk)
+        }
+    }
+
+    #[test]
+    fn test_injected_source() {
+        let hand = parse_cards("Ah Ad");
+        let source = Recording(Default::default());
+        let tally = tally_deals_from(
+            Deal::new(2, &hand, &[], 2),
+            Budget::iterations(12500),
+            &source,
+            |totals, deals| {
+                largest_error(
+                    totals.iter().map(|&total| total as f64 / deals as f64),
+                    deals,
+                )
+            },
+            |_, _, _| [1],
+        );
+        assert_eq!(tally.totals, [12500]);
+        let mut chunks = source.0.into_inner().unwrap();
+        chunks.sort();
+        assert_eq!(chunks, (0..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_exact_interval_is_the_rate() {
+        let hand = parse_cards("Ah 2c");
+        let board = parse_cards("Kh Kd 7c 7s 3d");
+        let equity = calculate_equity(Game::Holdem, &hand, &board, &[], 2, Budget::DEFAULT);
+        assert_eq!(equity.deals, 990);
+        assert_eq!(equity.win_interval(), (equity.win_rate, equity.win_rate));
+    }
+
+    #[test]
+    fn test_interval_of_a_rate_never_seen() {
+        // No tie in 1000 deals still leaves room for a rate of about 0.38%
+        let (low, high) = confidence_interval(0.0, 1000, false);
+        assert_eq!(low, 0.0);
+        assert!((high - 0.00383).abs() < 1e-5);
+        let (low, high) = confidence_interval(1.0, 1000, false);
+        assert!((low - (1.0 - 0.00383)).abs() < 1e-5);
+        assert_eq!(high, 1.0);
+    }
+
+    #[test]
+    fn test_all_in_every_seat() {
+        let hands = [
+            parse_cards("Ah Ad"),
+            parse_cards("Kh Kd"),
+            parse_cards("7c 8c"),
+        ];
+        let board = parse_cards("Ac Kc 2s 9d");
+        let seats = calculate_all_in(Game::Holdem, &hands, &board, &[], Budget::iterations(10000));
+        assert!(seats.iter().all(|seat| seat.exact));
+        // Of the 42 rivers, the last king gives quads, and a club that does not pair the
+        // board gives the flush against a set of aces
+        assert_eq!(seats[1].win_rate, 1.0 / 42.0);
+        assert_eq!(seats[2].win_rate, 7.0 / 42.0);
+        assert_eq!(seats[0].win_rate, 34.0 / 42.0);
+        for seat in &seats {
+            assert!((seat.win_rate + seat.tie_rate + seat.loss_rate - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_all_in_dead_card() {
+        let hands = [
+            parse_cards("Ah Ad"),
+            parse_cards("Kh Kd"),
+            parse_cards("7c 8c"),
+        ];
+        let board = parse_cards("Ac Kc 2s 9d");
+        // With the last king out of play, 41 rivers are left and the kings cannot win
+        let seats = calculate_all_in(
+            Game::Holdem,
+            &hands,
+            &board,
+            &parse_cards("Ks"),
+            Budget::iterations(10000),
+        );
+        assert_eq!(seats[1].win_rate, 0.0);
+        assert_eq!(seats[2].win_rate, 7.0 / 41.0);
+        assert_eq!(seats[0].win_rate, 34.0 / 41.0);
+    }
+
+    #[test]
+    fn test_all_in_split_board() {
+        let hands = [parse_cards("2c 3d"), parse_cards("2h 3s")];
+        let board = parse_cards("Ah Kh Qh Jh Th");
+        let seats = calculate_all_in(Game::Holdem, &hands, &board, &[], Budget::iterations(10000));
+        assert_eq!(seats[0].tie_rate, 1.0);
+        assert_eq!(seats[1].tie_rate, 1.0);
+        assert_eq!(seats[0].split_rate(2), 1.0);
+        assert_eq!(seats[1].pot_share, 0.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card")]
+    fn test_all_in_duplicate_card() {
+        calculate_all_in(
+            Game::Holdem,
+            &[parse_cards("Ah Ad"), parse_cards("Ah Kd")],
+            &[],
+            &[],
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    fn test_omaha_nuts_always_win() {
+        let hand = parse_cards("Ah Kh 2c 2d");
+        let board = parse_cards("Qh Jh Th");
+        let equity = calculate_equity(
+            Game::Omaha(4),
+            &hand,
+            &board,
+            &[],
+            3,
+            Budget::iterations(10000),
+        );
+        assert_eq!(
+            (equity.win_rate, equity.tie_rate, equity.loss_rate),
+            (1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid hand length")]
+    fn test_wrong_hand_length() {
+        calculate_equity(
+            Game::Omaha(5),
+            &parse_cards("Ah Kh 2c 2d"),
+            &[],
+            &[],
+            2,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid player count: expected 2 to 23 players, found 30")]
+    fn test_too_many_players() {
+        calculate_equity(
+            Game::Holdem,
+            &parse_cards("Ah Kd"),
+            &parse_cards("2c 3c 4c 5c 6d"),
+            &[],
+            30,
+            Budget::iterations(1000),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough cards to deal: 20 needed, 18 left")]
+    fn test_deck_too_small() {
+        // Ten players need twenty cards, but the Short Deck has only 18 left after the board
+        calculate_short_deck(
+            &parse_cards("?? ??"),
+            &parse_cards("Ah Kh Qh Jh Th"),
+            &parse_cards("9h 9d 9c 9s 8h 8d 8c 8s 7h 7d 7c 7s 6h"),
+            10,
+            Budget::iterations(1000),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid board length: expected at most 5 cards, found 6")]
+    fn test_board_too_long() {
+        calculate_equity(
+            Game::Holdem,
+            &parse_cards("Ah Kd"),
+            &parse_cards("2c 3c 4c 5c 6c 7c"),
+            &[],
+            2,
+            Budget::iterations(1000),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid board length: expected at most 5 cards, found 6")]
+    fn test_deal_board_too_long() {
+        Deal::new(2, &[], &parse_cards("2c 3c 4c 5c 6c 7c"), 2);
+    }
+
+    #[test]
+    fn test_hi_lo_no_low_possible() {
+        // No low can be made on this board, so the nut high scoops
+        let hand = parse_cards("Kh Kc 2c 3d");
+        let board = parse_cards("Ks Kd Qc Jh 9s");
+        let result = calculate_hi_lo(
+            Game::Omaha(4),
+            &hand,
+            &board,
+            &[],
+            3,
+            Budget::iterations(10000),
+        );
+        assert_eq!(result.scoop, 1.0);
+        assert_eq!(result.equity, 1.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: Ks")]
+    fn test_hi_lo_dead_card_on_board() {
+        calculate_hi_lo(
+            Game::Omaha(4),
+            &parse_cards("Kh Kc 2c 3d"),
+            &parse_cards("Ks Kd Qc"),
+            &parse_cards("Ks"),
+            2,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    fn test_hi_lo_chopped_high_without_a_low() {
+        // No low is possible, so an opponent with another ace-king chops the whole pot
+        let hand = parse_cards("Ah Kd");
+        let board = parse_cards("Qs Js Ts 9h 9c");
+        let result = calculate_hi_lo(
+            Game::Holdem,
+            &hand,
+            &board,
+            &[],
+            2,
+            Budget::iterations(10000),
+        );
+        assert!(result.exact);
+        assert_eq!(result.high_only, 0.0);
+        assert_eq!(result.low_only, 0.0);
+        assert!(result.quartered > 0.0);
+        assert!((result.equity - result.scoop - result.quartered / 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hi_lo_nut_high_and_nut_low() {
+        // Quad kings and the nut low, but opponents can tie the low with another ace and five
+        let hand = parse_cards("Kh Kc Ah 5s");
+        let board = parse_cards("Ks Kd 2c 3d 4h");
+        let result = calculate_hi_lo(
+            Game::Omaha(4),
+            &hand,
+            &board,
+            &[],
+            4,
+            Budget::iterations(10000),
+        );
+        assert_eq!(result.lose, 0.0);
+        assert_eq!(result.low_only, 0.0);
+        assert_eq!(result.high_only, 0.0);
+        assert!(result.scoop > 0.5);
+        assert!(result.quartered > 0.0);
+        assert_eq!(result.scoop + result.quartered, 1.0);
+        assert!(result.equity > 0.75 && result.equity < 1.0);
+    }
+
+    #[test]
+    fn test_hi_lo_deals_unknown_cards() {
+        // Quad kings scoop when no low can be made, whatever the unknown cards are
+        let hand = parse_cards("Kh Kc ?? ??");
+        let board = parse_cards("Ks Kd Qc Jh 9s");
+        let result = calculate_hi_lo(
+            Game::Omaha(4),
+            &hand,
+            &board,
+            &[],
+            2,
+            Budget::iterations(10000),
+        );
+        assert_eq!(result.scoop, 1.0);
+    }
+
+    #[test]
+    fn test_hi_lo_split_halves() {
+        // The hero holds the nut high but can never make a low, and the board makes one for
+        // anyone holding two low cards
+        let hand = parse_cards("Kh Kc Qh Qs");
+        let board = parse_cards("Ks Kd 2c 3d 4h");
+        let result = calculate_hi_lo(
+            Game::Omaha(4),
+            &hand,
+            &board,
+            &[],
+            2,
+            Budget::iterations(10000),
+        );
+        assert_eq!(result.lose, 0.0);
+        assert!(result.high_only > 0.0);
+        assert!(result.scoop > 0.0);
+        let total =
+            result.scoop + result.high_only + result.low_only + result.quartered + result.lose;
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_razz_wheel_never_loses() {
+        let hand = parse_cards("Ah 2d 3c 4s 5h Kd Kc");
+        let equity = calculate_razz(&hand, &[], 3, Budget::iterations(10000));
+        assert_eq!(equity.loss_rate, 0.0);
+        assert!(equity.win_rate > 0.9);
+        assert!((equity.win_rate + equity.tie_rate - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_razz_partial_hand() {
+        let good = calculate_razz(&parse_cards("Ah 2d 3c"), &[], 2, Budget::iterations(10000));
+        let bad = calculate_razz(&parse_cards("Kh Kd Qc"), &[], 2, Budget::iterations(10000));
+        assert!(good.win_rate > 0.6);
+        assert!(bad.win_rate < 0.4);
+        let (low, high) = good.win_interval();
+        assert!(!good.exact && low < good.win_rate && good.win_rate < high);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid hand length")]
+    fn test_razz_too_many_cards() {
+        calculate_razz(
+            &parse_cards("Ah 2d 3c 4s 5h 6d 7c 8h"),
+            &[],
+            2,
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card: 3c")]
+    fn test_razz_dead_card_in_hand() {
+        calculate_razz(
+            &parse_cards("Ah 2d 3c"),
+            &parse_cards("3c"),
+            2,
+            Budget::iterations(10),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown cards are not supported in Razz")]
+    fn test_razz_rejects_unknown_cards() {
+        calculate_razz(&parse_cards("Ah ?? 3c"), &[], 2, Budget::iterations(10));
+    }
+
+    #[test]
+    fn test_short_deck_deals_unknown_cards() {
+        // The ten of hearts makes a royal flush whatever the other card is
+        let hand = parse_cards("Th ??");
+        let board = parse_cards("Ah Kh Qh Jh 6c");
+        let equity = calculate_short_deck(&hand, &board, &[], 3, Budget::iterations(10000));
+        assert_eq!(equity.win_rate, 1.0);
+    }
+
+    #[test]
+    fn test_short_deck_flush_over_full_house() {
+        // The nut flush only loses to the last six for quads, while in Hold'em any pair
+        // or queen or jack would make a full house to beat it
+        let hand = parse_cards("Ah Kh");
+        let board = parse_cards("Qh Jh 6h 6d 6c");
+        let equity = calculate_short_deck(&hand, &board, &[], 3, Budget::iterations(10000));
+        assert!(equity.win_rate > 0.8);
+        assert_eq!(equity.tie_rate, 0.0);
+        assert!(equity.loss_rate < 0.2);
+    }
+
+    #[test]
+    fn test_short_deck_dead_cards_are_never_dealt() {
+        // 30 rivers and 406 opponent hands are left, or 28 and 351 with two more cards out
+        let hand = parse_cards("Ah Ad");
+        let board = parse_cards("As Kh Kd 9c");
+        let budget = Budget::iterations(20000);
+        let equity = calculate_short_deck(&hand, &board, &[], 2, budget);
+        assert_eq!(equity.deals, 30 * 406);
+        let dead = parse_cards("Ks Qs");
+        let equity = calculate_short_deck(&hand, &board, &dead, 2, budget);
+        assert!(equity.exact);
+        assert_eq!(equity.deals, 28 * 351);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid Short Deck card")]
+    fn test_short_deck_rejects_low_cards() {
+        calculate_short_deck(&parse_cards("Ah 2h"), &[], &[], 2, Budget::iterations(10));
+    }
+
+    #[test]
+    fn test_wild_five_of_a_kind() {
+        let hand = parse_cards("X1 Ah");
+        let board = parse_cards("As Ad Ac 7d 2c");
+        let equity = calculate_wild(
+            &hand,
+            &board,
+            &[],
+            3,
+            Wilds::jokers(2),
+            Budget::iterations(10000),
+        );
+        assert_eq!(equity.win_rate, 1.0);
+    }
+
+    #[test]
+    fn test_wild_dead_joker_is_never_dealt() {
+        // With the only joker out of play, 46 rivers and 990 opponent hands are left
+        let hand = parse_cards("Ah Kd");
+        let board = parse_cards("As Ad Ac 7d");
+        let equity = calculate_wild(
+            &hand,
+            &board,
+            &parse_cards("X1"),
+            2,
+            Wilds::jokers(1),
+            Budget::iterations(100000),
+        );
+        assert!(equity.exact);
+        assert_eq!(equity.deals, 46 * 990);
+    }
+
+    #[test]
+    fn test_wild_deals_unknown_cards() {
+        // The joker on the board makes five aces, whatever the other card is
+        let hand = parse_cards("Ah ??");
+        let board = parse_cards("As Ad Ac X1 2c");
+        let equity = calculate_wild(
+            &hand,
+            &board,
+            &[],
+            3,
+            Wilds::jokers(1),
+            Budget::iterations(10000),
+        );
+        assert_eq!(equity.win_rate, 1.0);
+    }
+
+    #[test]
+    fn test_wild_jokers_are_dealt() {
+        // Nothing beats quad kings here, but both jokers tie them
+        let hand = parse_cards("Kh Kd");
+        let board = parse_cards("Ks Kc 7d 2c 9h");
+        let budget = Budget::iterations(10000);
+        let natural = calculate_wild(&hand, &board, &[], 3, Wilds::NONE, budget);
+        assert_eq!(natural.win_rate, 1.0);
+        let wild = calculate_wild(&hand, &board, &[], 3, Wilds::jokers(2), budget);
+        assert!(wild.tie_rate > 0.0);
+        assert_eq!(wild.loss_rate, 0.0);
+    }
+
+    #[test]
+    fn test_hi_lo_interval() {
+        let hand = parse_cards("Ah 2h Kd Qd");
+        let board = parse_cards("3c 7s Kh");
+        let result = calculate_hi_lo(
+            Game::Omaha(4),
+            &hand,
+            &board,
+            &[],
+            3,
+            Budget::iterations(10000),
+        );
+        let (low, high) = result.interval(result.scoop);
+        assert!(!result.exact && result.deals == 10000);
+        assert!(low < result.scoop && result.scoop < high);
+    }
+}
@@ -0,0 +1,174 @@
+use std::iter;
+
+use rand::seq::SliceRandom;
+
+use crate::card::{check_known, Card};
+use crate::card_set::CardSet;
+use crate::lookup::evaluate_strength;
+use crate::simulation::{
+    rates_of, sample_deals, seat_error, split_showdown, tally_equity, Budget, Equity,
+};
+
+/// Estimates the hero's chances in Seven Card Stud against one opponent for each entry of
+/// `opponents`.
+///
+/// `hand` holds every card the hero has so far, downcards and upcards alike, and each
+/// opponent entry holds the upcards that player shows. The `dead` cards, such as the
+/// upcards of folded hands, are out of play. Every hand is then completed to seven cards
+/// from the rest of the deck, covering the unknown downcards and the streets still to come,
+/// and ranked like [`crate::evaluate_hand`] ranks seven cards.
+///
+/// Returns `(win_rate, tie_rate, loss_rate)`, each between 0 and 1.
+///
+/// # Panics
+///
+/// Panics if a player has more than 7 cards, if a card is given twice or is unknown, or if
+/// the deck cannot complete every hand.
+pub fn simulate_stud(hand: &[Card], opponents: &[Vec<Card>], dead: &[Card]) -> (f64, f64, f64) {
+    rates_of(calculate_stud(hand, opponents, dead, Budget::DEFAULT))
+}
+
+/// Like [`simulate_stud`], sampling random deals until `budget` is spent.
+///
+/// # Panics
+///
+/// Panics if a player has more than 7 cards, if a card is given twice or is unknown, or if
+/// the deck cannot complete every hand.
+pub fn calculate_stud(
+    hand: &[Card],
+    opponents: &[Vec<Card>],
+    dead: &[Card],
+    budget: Budget,
+) -> Equity {
+    let players: Vec<&[Card]> = iter::once(hand)
+        .chain(opponents.iter().map(Vec::as_slice))
+        .collect();
+    for cards in &players {
+        assert!(
+            cards.len() <= 7,
+            "Invalid stud hand length: expected at most 7 cards, found {}",
+            cards.len()
+        );
+    }
+    check_known(&players.concat(), "Stud");
+    check_known(dead, "Stud");
+    let mut known = CardSet::EMPTY;
+    for &card in players.iter().flat_map(|cards| cards.iter()).chain(dead) {
+        assert!(known.insert(card), "Duplicate card: {}", card);
+    }
+
+    let deck: Vec<Card> = (CardSet::full_deck() - known).iter().collect();
+    let cards_needed: usize = players.iter().map(|cards| 7 - cards.len()).sum();
+    assert!(
+        cards_needed <= deck.len(),
+        "Not enough cards to deal: {} needed, {} left",
+        cards_needed,
+        deck.len()
+    );
+
+    // Every player's seven cards one after another, with the known cards already in place
+    // and the positions of the rest in `open`
+    let placeholder = Card::UNKNOWN;
+    let table: Vec<Card> = players
+        .iter()
+        .flat_map(|cards| {
+            let missing = 7 - cards.len();
+            cards
+                .iter()
+                .copied()
+                .chain(iter::repeat_n(placeholder, missing))
+        })
+        .collect();
+    let open: Vec<usize> = players
+        .iter()
+        .enumerate()
+        .flat_map(|(player, cards)| (cards.len()..7).map(move |i| player * 7 + i))
+        .collect();
+
+    let tally = sample_deals(
+        budget,
+        seat_error,
+        || (deck.clone(), table.clone()),
+        |(deck, table), rng| {
+            let (dealt, _) = deck.partial_shuffle(rng, cards_needed);
+            for (&position, &card) in open.iter().zip(dealt.iter()) {
+                table[position] = card;
+            }
+
+            let mut hands = table.chunks(7).map(|cards| evaluate_strength(cards, &[]));
+            let hero = hands.next().unwrap();
+            split_showdown(hero, hands)
+        },
+    );
+    tally_equity(tally)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+
+    #[test]
+    fn test_royal_flush_never_loses() {
+        let hand = parse_cards("Ah Kh Qh Jh Th 2c 3d");
+        let opponents = vec![parse_cards("9s 9d"), vec![]];
+        let equity = calculate_stud(&hand, &opponents, &[], Budget::iterations(10000));
+        assert_eq!(equity.loss_rate, 0.0);
+    }
+
+    #[test]
+    fn test_opponent_upcards_are_known() {
+        // The opponent already shows four kings
+        let hand = parse_cards("Ah Ad Ac");
+        let opponents = vec![parse_cards("Kh Kd Ks Kc")];
+        let equity = calculate_stud(&hand, &opponents, &[], Budget::iterations(10000));
+        assert!(equity.win_rate < 0.1);
+    }
+
+    #[test]
+    fn test_dead_cards_change_the_odds() {
+        let hand = parse_cards("Ah Ad 5c");
+        let opponents = vec![parse_cards("Kh"), parse_cards("Qh")];
+        let budget = Budget::iterations(10000);
+        let dead_aces = calculate_stud(&hand, &opponents, &parse_cards("As Ac"), budget);
+        let dead_kings = calculate_stud(&hand, &opponents, &parse_cards("Ks Kc"), budget);
+        assert!(dead_aces.win_rate < dead_kings.win_rate);
+    }
+
+    #[test]
+    fn test_seeded_deals_repeat() {
+        let hand = parse_cards("Ah Ad 5c");
+        let opponents = vec![parse_cards("Kh"), vec![]];
+        let budget = Budget::iterations(5000).with_seed(3);
+        let equity = calculate_stud(&hand, &opponents, &[], budget);
+        assert_eq!(equity, calculate_stud(&hand, &opponents, &[], budget));
+    }
+
+    #[test]
+    #[should_panic(expected = "Duplicate card")]
+    fn test_duplicate_card() {
+        calculate_stud(
+            &parse_cards("Ah Ad"),
+            &[parse_cards("Kh")],
+            &parse_cards("Ah"),
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown cards are not supported in Stud")]
+    fn test_unknown_card() {
+        calculate_stud(
+            &parse_cards("Ah ?? 3c"),
+            &[parse_cards("Kh")],
+            &[],
+            Budget::iterations(1),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough cards to deal")]
+    fn test_too_many_players() {
+        calculate_stud(&[], &vec![vec![]; 7], &[], Budget::iterations(1));
+    }
+}
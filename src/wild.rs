@@ -0,0 +1,256 @@
+use crate::card::Card;
+use crate::evaluator::HandRank;
+use crate::lookup::evaluate_set;
+
+/// Which cards play as wild: every joker, and every card of a value declared wild.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Wilds {
+    // Number of jokers in the deck, from 0 to 4
+    pub jokers: u8,
+    // Bit `value` is set for each wild value, such as bit 2 for deuces wild
+    pub values: u16,
+}
+
+impl Wilds {
+    /// No wild cards, as in a standard game.
+    pub const NONE: Wilds = Wilds {
+        jokers: 0,
+        values: 0,
+    };
+
+    /// A deck with `jokers` jokers added.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 4 jokers.
+    pub fn jokers(jokers: u8) -> Wilds {
+        assert!(
+            jokers <= 4,
+            "Invalid joker count: expected at most 4, found {}",
+            jokers
+        );
+        Wilds { jokers, values: 0 }
+    }
+
+    /// Also declares every card of `value` wild, such as 2 for deuces wild.
+    pub fn with_wild_value(self, value: u8) -> Wilds {
+        Wilds {
+            values: self.values | 1 << value,
+            ..self
+        }
+    }
+
+    /// Whether `card` plays as wild.
+    pub fn is_wild(self, card: Card) -> bool {
+        card.is_joker() || self.values & (1 << card.value) != 0
+    }
+}
+
+/// Evaluates the best poker hand that can be made from `hand` and `board` together, where
+/// each wild card stands for whichever card makes the best hand.
+///
+/// A wild card can stand for any card, even one already in the hand, so five of a kind is
+/// possible and a wild card in a flush plays as its ace.
+///
+/// # Panics
+///
+/// Panics if fewer than 5 or more than 7 cards are given.
+pub fn evaluate_wild(hand: &[Card], board: &[Card], wilds: Wilds) -> HandRank {
+    let cards = || hand.iter().chain(board).copied();
+    let card_count = hand.len() + board.len();
+    assert!(
+        (5..=7).contains(&card_count),
+        "Invalid card count: expected 5 to 7 cards, found {}",
+        card_count
+    );
+
+    let wild_count = cards().filter(|&card| wilds.is_wild(card)).count();
+    if wild_count == 0 {
+        return evaluate_set(cards().collect()).rank();
+    }
+
+    // Counts of each natural value, and the natural values held in each suit as bit masks
+    let mut counts = [0; 15];
+    let mut suits = [0u16; 4];
+    for card in cards().filter(|&card| !wilds.is_wild(card)) {
+        counts[card.value as usize] += 1;
+        suits[card.suit as usize] |= 1 << card.value;
+    }
+    best_with_wilds(counts, suits, wild_count)
+}
+
+// The best hand from natural cards and `wilds` wild cards. Every category is tried from the
+// top down, and within a category the highest values that the wild cards can complete win.
+fn best_with_wilds(counts: [usize; 15], suits: [u16; 4], wilds: usize) -> HandRank {
+    let values = || (2..=14u8).rev();
+    let count = |value: u8| counts[value as usize];
+    // The highest natural values, each once, other than `except`
+    let kickers = |except: u8| values().filter(move |&value| value != except && count(value) > 0);
+
+    if let Some(five) = values().find(|&value| count(value) + wilds >= 5) {
+        return HandRank::FiveOfAKind(five);
+    }
+
+    if let Some(high) = suits
+        .iter()
+        .filter_map(|&suit| best_straight(suit, wilds))
+        .max()
+    {
+        return if high == 14 {
+            HandRank::RoyalFlush
+        } else {
+            HandRank::StraightFlush(high)
+        };
+    }
+
+    if let Some(quads) = values().find(|&value| count(value) + wilds >= 4) {
+        return HandRank::FourOfAKind(quads, kickers(quads).next().unwrap());
+    }
+
+    for triple in values() {
+        for pair in values().filter(|&pair| pair != triple) {
+            if 3usize.saturating_sub(count(triple)) + 2usize.saturating_sub(count(pair)) <= wilds {
+                return HandRank::FullHouse(triple, pair);
+            }
+        }
+    }
+
+    let best_flush = suits
+        .iter()
+        .filter(|suit| suit.count_ones() as usize + wilds >= 5)
+        .map(|&suit| {
+            let mut flush = [14; 5];
+            for (slot, value) in flush[wilds..]
+                .iter_mut()
+                .zip(values().filter(|value| suit & (1 << value) != 0))
+            {
+                *slot = value;
+            }
+            flush
+        })
+        .max();
+    if let Some([a, b, c, d, e]) = best_flush {
+        return HandRank::Flush(a, b, c, d, e);
+    }
+
+    let naturals = suits.iter().fold(0, |mask, &suit| mask | suit);
+    if let Some(high) = best_straight(naturals, wilds) {
+        return HandRank::Straight(high);
+    }
+
+    // Any natural card with two wild cards, or a natural pair with one, makes three of a kind
+    if let Some(triple) = values().find(|&value| count(value) + wilds >= 3) {
+        let mut rest = kickers(triple);
+        return HandRank::ThreeOfAKind(triple, rest.next().unwrap(), rest.next().unwrap());
+    }
+
+    // A single wild card with no natural pair pairs the highest card
+    let mut rest = values().filter(|&value| count(value) > 0);
+    let pair = rest.next().unwrap();
+    HandRank::OnePair(
+        pair,
+        rest.next().unwrap(),
+        rest.next().unwrap(),
+        rest.next().unwrap(),
+    )
+}
+
+// The highest straight that `wilds` wild cards can complete from a mask with bit `value` set
+// for each value held
+fn best_straight(mask: u16, wilds: usize) -> Option<u8> {
+    (5..=14u8).rev().find(|&high| {
+        let window: u16 = if high == 5 {
+            1 << 14 | 0b111100
+        } else {
+            0b11111 << (high - 4)
+        };
+        (window & !mask).count_ones() as usize <= wilds
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card::parse_cards;
+    use crate::deck::create_deck;
+    use crate::evaluator::evaluate_hand;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+    use rand::SeedableRng;
+
+    fn deuces_wild(cards: &str) -> HandRank {
+        evaluate_wild(&parse_cards(cards), &[], Wilds::NONE.with_wild_value(2))
+    }
+
+    fn jokers(cards: &str) -> HandRank {
+        evaluate_wild(&parse_cards(cards), &[], Wilds::jokers(2))
+    }
+
+    #[test]
+    fn test_no_wild_cards() {
+        assert_eq!(
+            deuces_wild("Ah Kd Qc 9s 7h"),
+            HandRank::HighCard(14, 13, 12, 9, 7)
+        );
+    }
+
+    #[test]
+    fn test_five_of_a_kind() {
+        assert_eq!(jokers("Ah Ad Ac As X1"), HandRank::FiveOfAKind(14));
+        assert_eq!(
+            deuces_wild("7h 7d 2c Kd 9s"),
+            HandRank::ThreeOfAKind(7, 13, 9)
+        );
+        assert_eq!(deuces_wild("7h 7d 2c 2s 2h Kd"), HandRank::FiveOfAKind(7));
+        assert_eq!(jokers("X1 X2 2c 2s 2h"), HandRank::FiveOfAKind(2));
+        assert!(HandRank::FiveOfAKind(2) > HandRank::RoyalFlush);
+    }
+
+    #[test]
+    fn test_wild_straights_and_flushes() {
+        assert_eq!(jokers("Ah Kh Qh Jh X1"), HandRank::RoyalFlush);
+        assert_eq!(jokers("9h 8h 6h 5h X1"), HandRank::StraightFlush(9));
+        assert_eq!(jokers("Ah 3d 4c 5s X1"), HandRank::Straight(5));
+        assert_eq!(jokers("Td Jc Qs Ah X1"), HandRank::Straight(14));
+        // The wild card plays as the ace of the flush suit, even next to a natural ace
+        assert_eq!(jokers("Ah 9h 7h 3h X1"), HandRank::Flush(14, 14, 9, 7, 3));
+    }
+
+    #[test]
+    fn test_wild_pairs() {
+        assert_eq!(jokers("Kh 9d 7c 4s X1"), HandRank::OnePair(13, 9, 7, 4));
+        assert_eq!(
+            jokers("Kh 9d 7c 4s X1 X2"),
+            HandRank::ThreeOfAKind(13, 9, 7)
+        );
+        assert_eq!(jokers("Kh Kd 7c 7s X1"), HandRank::FullHouse(13, 7));
+    }
+
+    #[test]
+    fn test_matches_every_substitution() {
+        // Substituting every possible card for each wild card and ranking the result with
+        // the plain evaluator gives the same best hand
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut deck = create_deck();
+        let wilds = Wilds::jokers(2);
+        for hand_index in 0..40 {
+            deck.shuffle(&mut rng);
+            let wild_count = hand_index % 2 + 1;
+            let mut cards: Vec<Card> = deck[..7 - wild_count].to_vec();
+            cards.extend((0..wild_count as u8).map(Card::joker));
+
+            let naturals = &cards[..7 - wild_count];
+            let mut best = None;
+            for first in create_deck() {
+                if wild_count == 1 {
+                    best = best.max(Some(evaluate_hand(naturals, &[first])));
+                } else {
+                    for second in create_deck() {
+                        best = best.max(Some(evaluate_hand(naturals, &[first, second])));
+                    }
+                }
+            }
+            assert_eq!(Some(evaluate_wild(&cards, &[], wilds)), best);
+        }
+    }
+}